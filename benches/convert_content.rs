@@ -0,0 +1,45 @@
+//! Microbenchmark for `convert_content` on a synthetic 10k-line document.
+//!
+//! Run with `cargo bench`. Uses a plain timing loop so no extra
+//! benchmarking dependency is required.
+
+use doc2quarto::convert_content;
+use std::hint::black_box;
+use std::time::Instant;
+
+const LINES: usize = 10_000;
+const ITERATIONS: u32 = 20;
+
+/// Builds a document mixing frontmatter, prose and admonitions.
+fn synthetic_document() -> String {
+    let mut doc = String::from("---\ntitle: \"Bench\"\nsidebar_position: 1\n---\n");
+    for i in 0..LINES {
+        match i % 10 {
+            0 => doc.push_str(":::note Heads up\n"),
+            5 => doc.push_str(":::\n"),
+            _ => doc.push_str("Some regular markdown text with a [link](https://example.com).\n"),
+        }
+    }
+    doc
+}
+
+fn main() {
+    let doc = synthetic_document();
+
+    // Warm up once so regex compilation is not counted
+    black_box(convert_content(&doc));
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(convert_content(black_box(&doc)));
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "convert_content: {} lines x {} iterations in {:?} ({:?}/iter)",
+        LINES,
+        ITERATIONS,
+        elapsed,
+        elapsed / ITERATIONS
+    );
+}
@@ -0,0 +1,133 @@
+//! Converts the MDX `<Admonition>` component to Docusaurus `:::` admonition syntax.
+//!
+//! ```text
+//! <Admonition type="tip" title="Pro tip">      :::tip[Pro tip]
+//! Content                               →      Content
+//! </Admonition>                                :::
+//! ```
+//!
+//! The rewritten blocks are converted to Quarto callouts together with the
+//! regular `:::` admonitions, so type mappings, titles and nesting behave the same.
+
+use crate::fence::FenceTracker;
+use crate::line_map::LineMap;
+use crate::tabs::prop;
+use regex::Regex;
+use std::sync::OnceLock;
+
+static ADMONITION_OPEN: OnceLock<Regex> = OnceLock::new();
+
+const CLOSING_TAG: &str = "</Admonition>";
+
+/// Rewrites every `<Admonition>` component in the content as a `:::` admonition.
+///
+/// The `type` prop becomes the admonition type (defaulting to `note`) and the
+/// `title` prop its `[label]`. Props may span several lines, and a component
+/// written on one line becomes a single-line admonition. Lines inside fenced
+/// code blocks are never converted.
+///
+/// # Arguments
+/// - `content`: The complete content of the markdown file as a string
+///
+/// # Returns
+/// The content with admonition components replaced by `:::` blocks
+pub fn convert_admonition_components(content: &str) -> String {
+    convert_admonition_components_mapped(content).0
+}
+
+/// Same as `convert_admonition_components`, also returning which source line each output line comes from.
+pub(crate) fn convert_admonition_components_mapped(content: &str) -> (String, LineMap) {
+    let mut result = String::with_capacity(content.len());
+    let mut map = LineMap::default();
+    let mut last_index = 0;
+    let mut fences = FenceTracker::default();
+    // Indentation of every open component, reused for its closing fence
+    let mut open: Vec<String> = Vec::new();
+    // (indentation, props read so far) of an opening tag whose props span several lines
+    let mut pending: Option<(String, String)> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        // Whatever the previous line wrote comes from it
+        map.track(&result, index.saturating_sub(1));
+        last_index = index;
+        if let Some((indent, mut props)) = pending.take() {
+            match line.split_once('>') {
+                Some((last, rest)) => {
+                    props.push(' ');
+                    props.push_str(last);
+                    open_admonition(&mut result, &mut open, &indent, &props, rest);
+                }
+                None => {
+                    props.push(' ');
+                    props.push_str(line);
+                    pending = Some((indent, props));
+                }
+            }
+            continue;
+        }
+
+        if fences.update(line) {
+            result.push_str(line);
+            result.push('\n');
+            continue;
+        }
+
+        if let Some(caps) = admonition_open().captures(line) {
+            let indent = caps["indent"].to_string();
+            if caps["close"].is_empty() {
+                pending = Some((indent, caps["props"].to_string()));
+            } else {
+                open_admonition(&mut result, &mut open, &indent, &caps["props"], &caps["rest"]);
+            }
+            continue;
+        }
+
+        if let Some(body) = line.trim_end().strip_suffix(CLOSING_TAG)
+            && let Some(indent) = open.pop()
+        {
+            if !body.trim().is_empty() {
+                result.push_str(body.trim_end());
+                result.push('\n');
+            }
+            result.push_str(&indent);
+            result.push_str(":::\n");
+            continue;
+        }
+
+        result.push_str(line);
+        result.push('\n');
+    }
+
+    map.finish(&result, last_index);
+    (result, map)
+}
+
+/// Emits the `:::type[title]` line for an opening tag, plus any content after the tag.
+fn open_admonition(result: &mut String, open: &mut Vec<String>, indent: &str, props: &str, rest: &str) {
+    let admonition_type = prop(props, "type").unwrap_or_else(|| "note".to_string());
+    let mut opening = format!("{}:::{}", indent, admonition_type);
+    if let Some(title) = prop(props, "title") {
+        opening.push_str(&format!("[{}]", title));
+    }
+
+    let rest = rest.trim();
+    // `<Admonition type="tip">Short</Admonition>` becomes a single-line admonition
+    if let Some(body) = rest.strip_suffix(CLOSING_TAG) {
+        result.push_str(&format!("{} {} :::\n", opening, body.trim()));
+        return;
+    }
+
+    result.push_str(&opening);
+    result.push('\n');
+    if !rest.is_empty() {
+        result.push_str(indent);
+        result.push_str(rest);
+        result.push('\n');
+    }
+    open.push(indent.to_string());
+}
+
+fn admonition_open() -> &'static Regex {
+    ADMONITION_OPEN
+        .get_or_init(|| Regex::new(r"^(?P<indent>[ \t]*)<Admonition\b(?P<props>[^>]*)(?P<close>>?)(?P<rest>.*)$").unwrap())
+}
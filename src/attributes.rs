@@ -0,0 +1,6 @@
+//! Quoting for the values of Quarto/Pandoc attributes (`{.callout-note title="..."}`).
+
+/// Escapes a value for use inside a double-quoted Quarto attribute.
+pub(crate) fn escape_attribute(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
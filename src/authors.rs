@@ -0,0 +1,84 @@
+//! Converts Docusaurus blog authors to Quarto's `author` field.
+//!
+//! Docusaurus posts list their authors in `authors`, either inline
+//! (`authors: {name: Jane, title: Maintainer, url: https://...}`) or as keys
+//! into the blog's `authors.yml` (`authors: [jane]`). Quarto reads a single
+//! `author` field holding names or author objects, so slugs are resolved
+//! against the authors map and each author is translated to Quarto's schema:
+//!
+//! - `name`, `url` and `email` keep their names
+//! - `title` becomes the author's `note`
+//! - `image_url`, `image`, `socials` and `page` are dropped, Quarto has no equivalent
+
+use crate::ConversionError;
+use log::warn;
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::Path;
+
+/// Loads a Docusaurus `authors.yml` file, mapping author slugs to author objects.
+///
+/// # Errors
+/// - `ConversionError::ReadFailed` if the file cannot be read
+/// - `ConversionError::InvalidAuthors` if it is not a YAML mapping
+pub fn load_authors(path: &Path) -> Result<Mapping, ConversionError> {
+    let source = fs::read_to_string(path).map_err(|source| ConversionError::ReadFailed { path: path.to_path_buf(), source })?;
+    if source.trim().is_empty() {
+        return Ok(Mapping::new());
+    }
+    serde_yaml::from_str(&source).map_err(|source| ConversionError::InvalidAuthors { path: path.to_path_buf(), source })
+}
+
+/// Converts a Docusaurus `authors` value to the value of Quarto's `author` field.
+///
+/// # Arguments
+/// - `value`: The `authors` value, a slug, an author object or a list of either
+/// - `authors`: Author objects keyed by slug, as loaded by `load_authors`
+///
+/// # Returns
+/// A list of Quarto authors. Slugs missing from `authors` are kept as plain
+/// names with a warning.
+pub fn convert_authors(value: Value, authors: &Mapping) -> Value {
+    let entries = match value {
+        Value::Sequence(entries) => entries,
+        other => vec![other],
+    };
+    Value::Sequence(entries.into_iter().map(|entry| convert_author(entry, authors)).collect())
+}
+
+/// Converts a single author slug or inline author object.
+fn convert_author(entry: Value, authors: &Mapping) -> Value {
+    match entry {
+        Value::String(slug) => match authors.get(slug.as_str()) {
+            Some(Value::Mapping(author)) => quarto_author(author),
+            _ => {
+                if !authors.is_empty() {
+                    warn!("  ⚠ Author `{}` is not in the authors map, keeping it as a name", slug);
+                }
+                Value::String(slug)
+            }
+        },
+        Value::Mapping(author) => quarto_author(&author),
+        other => other,
+    }
+}
+
+/// Translates a Docusaurus author object to Quarto's author schema.
+pub(crate) fn quarto_author(author: &Mapping) -> Value {
+    let mut converted = Mapping::new();
+    for (key, value) in author {
+        match key.as_str() {
+            Some("name" | "url" | "email") => {
+                converted.insert(key.clone(), value.clone());
+            }
+            Some("title") => {
+                converted.insert(Value::from("note"), value.clone());
+            }
+            Some("image_url" | "imageURL" | "image" | "socials" | "page" | "key") => {}
+            _ => {
+                converted.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    Value::Mapping(converted)
+}
@@ -0,0 +1,74 @@
+//! Docusaurus blog conventions that need translating for Quarto listings.
+//!
+//! Docusaurus marks the end of a post's summary with `<!-- truncate -->`.
+//! Quarto listings show the `description` field instead, so the summary can be
+//! promoted into the frontmatter and the marker removed.
+
+use crate::fence::FenceTracker;
+use crate::frontmatter::frontmatter_delimiter;
+
+/// How `<!-- truncate -->` markers are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TruncateMarker {
+    /// Leave the marker in place; Quarto renders it as an invisible HTML comment
+    #[default]
+    Keep,
+    /// Remove the marker
+    Remove,
+    /// Remove the marker and use the text before it as the `description`
+    /// frontmatter field, unless the post already has one
+    Description,
+}
+
+/// Returns true if the line is a Docusaurus `<!-- truncate -->` marker.
+pub fn is_truncate_marker(line: &str) -> bool {
+    line.trim()
+        .strip_prefix("<!--")
+        .and_then(|rest| rest.strip_suffix("-->"))
+        .is_some_and(|inner| inner.trim() == "truncate")
+}
+
+/// Extracts the summary text before the `<!-- truncate -->` marker.
+///
+/// Frontmatter, headings and fenced code blocks are skipped; the remaining
+/// lines are joined into a single line of text.
+///
+/// # Arguments
+/// - `content`: The complete content of the markdown file as a string
+///
+/// # Returns
+/// - `Some(summary)` if the content has a truncate marker preceded by text
+/// - `None` if there is no marker or nothing but headings/code before it
+pub fn truncate_summary(content: &str) -> Option<String> {
+    let mut lines = content.lines().peekable();
+
+    // Skip a leading frontmatter block
+    while lines.peek().is_some_and(|l| l.trim().is_empty()) {
+        lines.next();
+    }
+    if let Some(delimiter) = lines.peek().and_then(|l| frontmatter_delimiter(l)) {
+        lines.next();
+        for line in lines.by_ref() {
+            if frontmatter_delimiter(line) == Some(delimiter) {
+                break;
+            }
+        }
+    }
+
+    let mut fences = FenceTracker::default();
+    let mut summary: Vec<&str> = Vec::new();
+    for line in lines {
+        if fences.update(line) {
+            continue;
+        }
+        if is_truncate_marker(line) {
+            return if summary.is_empty() { None } else { Some(summary.join(" ")) };
+        }
+        let trimmed = line.trim();
+        if !trimmed.is_empty() && !trimmed.starts_with('#') {
+            summary.push(trimmed);
+        }
+    }
+
+    None
+}
@@ -0,0 +1,125 @@
+//! Converts Docusaurus `_category_.json` sidebar categories to Quarto section pages.
+//!
+//! Docusaurus labels and orders a docs folder in the sidebar with a
+//! `_category_.json` (or `_category_.yml`) file inside it. Quarto's automatic
+//! sidebar takes a section's title and position from the `index.qmd` of the
+//! folder instead, so each category becomes an `index.qmd` holding only frontmatter:
+//!
+//! - `label` → `title`
+//! - `position` → `order`
+//! - `link.description` → `description`
+//! - `link.type: generated-index` → `listing: default`, Quarto's list of the section's pages
+//!
+//! Folders that already have an `index.md`/`index.mdx` keep that page.
+
+use crate::{index_pages, versions, ConvertOptions, ExistingOutput};
+use log::{debug, info, warn};
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// File names Docusaurus reads sidebar category metadata from.
+pub const CATEGORY_FILES: &[&str] = &["_category_.json", "_category_.yml", "_category_.yaml"];
+
+/// Converts the content of a category file to the `index.qmd` of its section.
+///
+/// The content is parsed as YAML, which also accepts the JSON form.
+///
+/// # Returns
+/// - `Ok(Some(page))` with the section page, frontmatter delimiters included
+/// - `Ok(None)` if the category sets nothing Quarto can use
+/// - `Err` if the content is not a YAML/JSON object
+pub fn convert_category(content: &str) -> Result<Option<String>, serde_yaml::Error> {
+    let category: Mapping = serde_yaml::from_str(content)?;
+    let mut frontmatter = Mapping::new();
+
+    if let Some(label) = category.get("label") {
+        frontmatter.insert(Value::from("title"), label.clone());
+    }
+    if let Some(position) = category.get("position") {
+        frontmatter.insert(Value::from("order"), position.clone());
+    }
+    if let Some(Value::Mapping(link)) = category.get("link") {
+        if let Some(description) = link.get("description") {
+            frontmatter.insert(Value::from("description"), description.clone());
+        }
+        if link.get("type").and_then(Value::as_str) == Some("generated-index") {
+            frontmatter.insert(Value::from("listing"), Value::from("default"));
+        }
+    }
+
+    if frontmatter.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(format!("---\n{}---\n", serde_yaml::to_string(&frontmatter)?)))
+}
+
+/// Writes the section page of every category file under `source_root`.
+///
+/// Pages go where the folder's documents go, versioned docs included. Only
+/// folders that receive converted documents (a prefix of one of `dest_paths`)
+/// get a page, and none when `opts.flatten` removes the folders. Existing pages
+/// follow `opts.existing_output` like documents do.
+/// Unreadable or invalid category files are skipped with a warning rather
+/// than failing the run.
+pub(crate) fn write_section_pages(source_root: &Path, dest_root: &Path, dest_paths: &[PathBuf], opts: &ConvertOptions) {
+    if opts.flatten {
+        return;
+    }
+    let mut categories: Vec<PathBuf> = WalkDir::new(source_root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && CATEGORY_FILES.iter().any(|name| e.file_name() == *name))
+        .map(|e| e.path().to_path_buf())
+        .collect();
+    categories.sort();
+
+    for category in categories {
+        let Some(source_dir) = category.parent() else { continue };
+        let relative_dir = source_dir.strip_prefix(source_root).unwrap_or(Path::new(""));
+        let dest_dir = dest_root.join(versions::output_relative_path(relative_dir, opts.versioned_docs));
+        if !dest_paths.iter().any(|path| path.starts_with(&dest_dir)) {
+            continue;
+        }
+        if index_pages::has_index_page(source_dir, opts.readme_as_index) {
+            debug!("  🗂 {:?} has an index page, ignoring {:?}", source_dir, category);
+            continue;
+        }
+
+        let content = match fs::read_to_string(&category) {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("  ⚠ Skipping sidebar category {:?}: {}", category, e);
+                continue;
+            }
+        };
+        let page = match convert_category(&content) {
+            Ok(Some(page)) => page,
+            Ok(None) => continue,
+            Err(e) => {
+                warn!("  ⚠ Skipping sidebar category {:?}: {}", category, e);
+                continue;
+            }
+        };
+
+        let index = dest_dir.join("index").with_extension(&opts.output_extension);
+        let exists = index.exists();
+        if opts.dry_run || opts.diff || (exists && opts.existing_output == ExistingOutput::Skip) {
+            continue;
+        }
+        if exists && opts.existing_output == ExistingOutput::Backup {
+            let mut backup = index.as_os_str().to_owned();
+            backup.push(".bak");
+            if let Err(e) = fs::rename(&index, &backup) {
+                warn!("  ⚠ Failed to back up section page {:?}: {}", index, e);
+                continue;
+            }
+            info!("  💾 Backed up existing file to {:?}", backup);
+        }
+        match fs::create_dir_all(&dest_dir).and_then(|_| fs::write(&index, page)) {
+            Ok(()) => info!("  🗂 Wrote section page {:?} from {:?}", index, category),
+            Err(e) => warn!("  ⚠ Failed to write section page {:?}: {}", index, e),
+        }
+    }
+}
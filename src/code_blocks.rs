@@ -0,0 +1,77 @@
+//! Converts Docusaurus code block metadata to Quarto code block attributes.
+//!
+//! ```text
+//! ```js title="example.js" {1,3-5}    →    ```{.js filename="example.js" code-line-numbers="1,3-5"}
+//! ```
+//!
+//! `showLineNumbers` becomes `code-line-numbers="true"` when no highlight range
+//! is given. Fences without Docusaurus metadata are left untouched.
+
+use crate::attributes::escape_attribute;
+use crate::fence::fence_marker;
+use regex::Regex;
+use std::sync::OnceLock;
+
+static INFO_TOKEN: OnceLock<Regex> = OnceLock::new();
+
+/// Rewrites the info string of an opening code fence line.
+///
+/// # Arguments
+/// - `line`: The opening fence line of a code block
+///
+/// # Returns
+/// The fence with Quarto attributes, or the line unchanged if it carries no
+/// `title=`, `{lines}` or `showLineNumbers` metadata
+pub fn convert_code_fence(line: &str) -> String {
+    let Some((ch, len, info)) = fence_marker(line) else {
+        return line.to_string();
+    };
+
+    let mut language = None;
+    let mut title = None;
+    let mut highlight = None;
+    let mut line_numbers = false;
+    let mut others = Vec::new();
+
+    for (i, caps) in info_token().captures_iter(info).enumerate() {
+        if let Some(value) = caps.name("title").or_else(|| caps.name("title2")) {
+            title = Some(value.as_str());
+        } else if let Some(lines) = caps.name("lines") {
+            highlight = Some(lines.as_str().replace(' ', ""));
+        } else {
+            let token = caps.get(0).map(|m| m.as_str()).unwrap_or("");
+            match token {
+                "showLineNumbers" => line_numbers = true,
+                token if i == 0 && !token.contains('=') => language = Some(token),
+                token => others.push(token),
+            }
+        }
+    }
+
+    if title.is_none() && highlight.is_none() && !line_numbers {
+        return line.to_string();
+    }
+
+    let mut attributes: Vec<String> = Vec::new();
+    if let Some(language) = language {
+        attributes.push(format!(".{}", language));
+    }
+    if let Some(title) = title {
+        attributes.push(format!("filename=\"{}\"", escape_attribute(title)));
+    }
+    match highlight {
+        Some(lines) => attributes.push(format!("code-line-numbers=\"{}\"", lines)),
+        None if line_numbers => attributes.push("code-line-numbers=\"true\"".to_string()),
+        None => {}
+    }
+    attributes.extend(others.into_iter().map(str::to_string));
+
+    let indent = &line[..line.len() - line.trim_start().len()];
+    format!("{}{}{{{}}}", indent, ch.to_string().repeat(len), attributes.join(" "))
+}
+
+fn info_token() -> &'static Regex {
+    INFO_TOKEN.get_or_init(|| {
+        Regex::new(r#"title=(?:"(?P<title>[^"]*)"|'(?P<title2>[^']*)')|\{(?P<lines>[\d,\s-]+)\}|\S+"#).unwrap()
+    })
+}
@@ -0,0 +1,32 @@
+//! HTML comment handling.
+//!
+//! Ordinary HTML comments are kept, Pandoc passes them through to the output.
+//! Comments that only direct tooling around a Docusaurus site, such as
+//! `<!-- prettier-ignore -->` in front of an admonition, mean nothing to Quarto
+//! and are removed. `<!-- truncate -->` is handled separately, see `TruncateMarker`.
+
+/// Comment directives removed from the converted output.
+const TOOLING_DIRECTIVES: &[&str] = &[
+    "prettier-ignore",
+    "prettier-ignore-start",
+    "prettier-ignore-end",
+];
+
+/// Returns true if the line is a comment-only tooling directive such as `<!-- prettier-ignore -->`.
+pub fn is_comment_directive(line: &str) -> bool {
+    comment_body(line).is_some_and(|body| TOOLING_DIRECTIVES.contains(&body))
+}
+
+/// Returns the trimmed text of a line that consists of a single `<!-- ... -->` comment.
+pub(crate) fn comment_body(line: &str) -> Option<&str> {
+    let inner = line.trim().strip_prefix("<!--")?.strip_suffix("-->")?;
+    if inner.contains("-->") {
+        return None;
+    }
+    Some(inner.trim())
+}
+
+/// Returns true if the line opens an HTML comment that continues on later lines.
+pub(crate) fn opens_multiline_comment(line: &str) -> bool {
+    line.rfind("<!--").is_some_and(|start| !line[start..].contains("-->"))
+}
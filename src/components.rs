@@ -0,0 +1,175 @@
+//! Converts small custom MDX components to Quarto spans and divs through a template registry.
+//!
+//! Docusaurus tutorials often define one-off components such as
+//! `<Highlight color="#25c2a0">text</Highlight>`. Each registered component name
+//! maps to a template of Quarto markup, in which `{children}` stands for the
+//! component's content and `{prop}` for the value of a string prop:
+//!
+//! ```text
+//! Highlight = '[{children}]{style="background-color: {color}"}'
+//!
+//! <Highlight color="#25c2a0">Docusaurus green</Highlight>  →  [Docusaurus green]{style="background-color: #25c2a0"}
+//! ```
+//!
+//! Only `{name}` with a plain identifier is a placeholder, so Pandoc attributes
+//! such as `{.class}` or `{style="..."}` are copied as written. A component whose
+//! tags stand on their own lines is a block: the template part before
+//! `{children}` replaces the opening tag and the part after it the closing tag.
+//! Components without a template are left alone and reported as residual
+//! components by the caller.
+
+use crate::fence::{FenceTracker, map_outside_inline_code};
+use crate::line_map::LineMap;
+use crate::tabs::prop;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+static COMPONENT_TAG: OnceLock<Regex> = OnceLock::new();
+static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+
+/// Templates for components that are common across Docusaurus sites, used unless configured otherwise.
+pub const BUILTIN_COMPONENTS: &[(&str, &str)] = &[("Highlight", r#"[{children}]{style="background-color: {color}"}"#)];
+
+const CHILDREN: &str = "{children}";
+
+/// Rewrites every registered component in the content with its template.
+///
+/// `custom` templates are looked up first, then `BUILTIN_COMPONENTS`. Lines
+/// inside fenced code blocks and inline code spans are never converted.
+///
+/// # Arguments
+/// - `content`: The complete content of the markdown file as a string
+/// - `custom`: Component name → template mappings, e.g. from a config file
+///
+/// # Returns
+/// The content with registered components replaced by their Quarto markup
+pub fn convert_components(content: &str, custom: &BTreeMap<String, String>) -> String {
+    convert_components_mapped(content, custom).0
+}
+
+/// Same as `convert_components`, also returning which source line each output line comes from.
+pub(crate) fn convert_components_mapped(content: &str, custom: &BTreeMap<String, String>) -> (String, LineMap) {
+    let mut result = String::with_capacity(content.len());
+    let mut map = LineMap::default();
+    let mut last_index = 0;
+    let mut fences = FenceTracker::default();
+    // (name, indentation, closing markup) of every open block component
+    let mut open: Vec<(String, String, String)> = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        // Whatever the previous line wrote comes from it
+        map.track(&result, index.saturating_sub(1));
+        last_index = index;
+        if fences.update(line) {
+            result.push_str(line);
+            result.push('\n');
+            continue;
+        }
+
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let trimmed = line.trim();
+
+        if let Some((name, _, _)) = open.last()
+            && trimmed.strip_prefix("</").and_then(|rest| rest.strip_suffix('>')) == Some(name.as_str())
+        {
+            let (_, indent, closing) = open.pop().unwrap_or_default();
+            if !closing.is_empty() {
+                result.push_str(&indent);
+                result.push_str(&closing);
+                result.push('\n');
+            }
+            continue;
+        }
+
+        if let Some(caps) = component_tag().captures(trimmed)
+            && caps.get(0).is_some_and(|m| m.len() == trimmed.len())
+            && caps["close"].is_empty()
+            && let Some(template) = template(&caps["name"], custom)
+        {
+            let filled = fill_props(template, &caps["props"]);
+            let (opening, closing) = filled.split_once(CHILDREN).unwrap_or((&filled, ""));
+            let (opening, closing) = (opening.trim_end_matches('\n'), closing.trim_start_matches('\n'));
+            if !opening.is_empty() {
+                result.push_str(indent);
+                result.push_str(opening);
+                result.push('\n');
+            }
+            open.push((caps["name"].to_string(), indent.to_string(), closing.to_string()));
+            continue;
+        }
+
+        result.push_str(&map_outside_inline_code(line, |_, text| convert_inline(text, custom)));
+        result.push('\n');
+    }
+
+    map.finish(&result, last_index);
+    if !content.ends_with('\n') {
+        result.pop();
+    }
+    (result, map)
+}
+
+/// Converts the registered components that open and close within `text`.
+fn convert_inline(text: &str, custom: &BTreeMap<String, String>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(caps) = component_tag().captures(rest) {
+        let Some(tag) = caps.get(0) else { break };
+        let name = &caps["name"];
+        let Some(template) = template(name, custom) else {
+            result.push_str(&rest[..tag.end()]);
+            rest = &rest[tag.end()..];
+            continue;
+        };
+
+        let after = &rest[tag.end()..];
+        let (children, remaining) = if caps["close"].is_empty() {
+            let closing_tag = format!("</{}>", name);
+            match after.find(&closing_tag) {
+                Some(end) => (&after[..end], &after[end + closing_tag.len()..]),
+                // Closed on a later line, which only block components may do
+                None => {
+                    result.push_str(&rest[..tag.end()]);
+                    rest = after;
+                    continue;
+                }
+            }
+        } else {
+            ("", after)
+        };
+
+        result.push_str(&rest[..tag.start()]);
+        let children = convert_inline(children, custom);
+        result.push_str(&fill_props(template, &caps["props"]).replace(CHILDREN, children.trim()));
+        rest = remaining;
+    }
+
+    result.push_str(rest);
+    result
+}
+
+fn template<'a>(name: &str, custom: &'a BTreeMap<String, String>) -> Option<&'a str> {
+    custom
+        .get(name)
+        .map(String::as_str)
+        .or_else(|| BUILTIN_COMPONENTS.iter().find(|(builtin, _)| *builtin == name).map(|(_, template)| *template))
+}
+
+/// Replaces every `{prop}` placeholder but `{children}` with the prop's value, empty if the prop is missing.
+fn fill_props(template: &str, props: &str) -> String {
+    placeholder()
+        .replace_all(template, |caps: &regex::Captures| {
+            if &caps[1] == "children" { CHILDREN.to_string() } else { prop(props, &caps[1]).unwrap_or_default() }
+        })
+        .into_owned()
+}
+
+fn component_tag() -> &'static Regex {
+    COMPONENT_TAG.get_or_init(|| Regex::new(r"<(?P<name>[A-Z][A-Za-z0-9_.]*)(?P<props>(?:\s[^>]*?)?)\s*(?P<close>/?)>").unwrap())
+}
+
+fn placeholder() -> &'static Regex {
+    PLACEHOLDER.get_or_init(|| Regex::new(r"\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap())
+}
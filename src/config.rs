@@ -0,0 +1,121 @@
+//! Project configuration loaded from a TOML or YAML file.
+//!
+//! ```toml
+//! [admonitions]
+//! hint = "tip"
+//! secret = "important"
+//!
+//! # Merged into every converted file's frontmatter
+//! [metadata]
+//! format = "html"
+//!
+//! # Quarto markup for custom MDX components, `{children}` is the content
+//! [components]
+//! Highlight = '[{children}]{style="color: {color}"}'
+//!
+//! # Replaces the default list of frontmatter keys to remove
+//! [frontmatter]
+//! drop = ["hide_title", "custom_edit_url"]
+//! ```
+//!
+//! The same structure can be written as YAML (`admonitions:` mapping). Files
+//! ending in `.toml` are read as TOML, everything else as YAML.
+
+use crate::frontmatter::toml_to_yaml;
+use crate::ConfigError;
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Settings read from a `--config` file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    /// Custom admonition → Quarto callout type mappings, keyed by lowercase admonition name
+    pub admonitions: BTreeMap<String, String>,
+    /// Custom MDX component → Quarto markup templates, keyed by component name
+    pub components: BTreeMap<String, String>,
+    /// Frontmatter metadata merged into every converted file
+    pub metadata: Mapping,
+    /// Frontmatter keys to remove instead of the default list, if configured
+    pub dropped_keys: Option<Vec<String>>,
+}
+
+impl Config {
+    /// Loads a config file, choosing the format from its extension.
+    ///
+    /// # Errors
+    /// - `ConfigError::ReadFailed` if the file cannot be read
+    /// - `ConfigError::InvalidFile` if it is not a valid config, with the parse error as its source
+    pub fn load(path: &Path) -> Result<Config, ConfigError> {
+        let source = fs::read_to_string(path).map_err(|source| ConfigError::ReadFailed { path: path.to_path_buf(), source })?;
+        let config = match path.extension().and_then(|s| s.to_str()) {
+            Some("toml") => Config::from_toml_str(&source),
+            _ => Config::from_yaml_str(&source),
+        };
+        config.map_err(|source| ConfigError::InvalidFile { path: path.to_path_buf(), source: Box::new(source) })
+    }
+
+    /// Parses a config from TOML text.
+    pub fn from_toml_str(source: &str) -> Result<Config, ConfigError> {
+        let table = source.parse::<toml::Table>()?;
+        match toml_to_yaml(toml::Value::Table(table)) {
+            Value::Mapping(mapping) => Config::from_mapping(&mapping),
+            _ => Ok(Config::default()),
+        }
+    }
+
+    /// Parses a config from YAML text.
+    pub fn from_yaml_str(source: &str) -> Result<Config, ConfigError> {
+        if source.trim().is_empty() {
+            return Ok(Config::default());
+        }
+        let mapping: Mapping = serde_yaml::from_str(source)?;
+        Config::from_mapping(&mapping)
+    }
+
+    fn from_mapping(mapping: &Mapping) -> Result<Config, ConfigError> {
+        let mut config = Config::default();
+
+        if let Some(admonitions) = mapping.get("admonitions") {
+            let Value::Mapping(admonitions) = admonitions else {
+                return Err(ConfigError::InvalidSetting("`admonitions` must map admonition names to callout types".to_string()));
+            };
+            for (name, callout) in admonitions {
+                let (Some(name), Some(callout)) = (name.as_str(), callout.as_str()) else {
+                    return Err(ConfigError::InvalidSetting(format!("invalid admonition mapping {:?}: {:?}", name, callout)));
+                };
+                config.admonitions.insert(name.to_lowercase(), callout.to_string());
+            }
+        }
+
+        if let Some(components) = mapping.get("components") {
+            let Value::Mapping(components) = components else {
+                return Err(ConfigError::InvalidSetting("`components` must map component names to Quarto markup templates".to_string()));
+            };
+            for (name, template) in components {
+                let (Some(name), Some(template)) = (name.as_str(), template.as_str()) else {
+                    return Err(ConfigError::InvalidSetting(format!("invalid component mapping {:?}: {:?}", name, template)));
+                };
+                config.components.insert(name.to_string(), template.to_string());
+            }
+        }
+
+        if let Some(metadata) = mapping.get("metadata") {
+            let Value::Mapping(metadata) = metadata else {
+                return Err(ConfigError::InvalidSetting("`metadata` must be a mapping of frontmatter keys".to_string()));
+            };
+            config.metadata = metadata.clone();
+        }
+
+        if let Some(drop) = mapping.get("frontmatter").and_then(|frontmatter| frontmatter.get("drop")) {
+            let keys = drop
+                .as_sequence()
+                .and_then(|keys| keys.iter().map(|key| key.as_str().map(str::to_string)).collect::<Option<Vec<_>>>())
+                .ok_or_else(|| ConfigError::InvalidSetting("`frontmatter.drop` must be a list of frontmatter keys".to_string()))?;
+            config.dropped_keys = Some(keys);
+        }
+
+        Ok(config)
+    }
+}
@@ -0,0 +1,154 @@
+//! Builder-style entry point for library use.
+//!
+//! ```
+//! use doc2quarto::Converter;
+//!
+//! let converter = Converter::new().with_link_rewriting(true).with_image_copy(false);
+//! let qmd = converter.convert_str(":::tip\nSee [setup](setup.md)\n:::\n");
+//! assert_eq!(qmd, ":::: {.callout-tip}\nSee [setup](setup.qmd)\n::::\n");
+//! ```
+
+use crate::{
+    convert_content_with_options, convert_tree, process_files, CalloutTitleStyle, ConversionError, ConversionReport, ConvertOptions,
+};
+use std::path::Path;
+
+/// Converts Docusaurus markdown to Quarto with a fixed set of options.
+///
+/// Every setting starts at its `ConvertOptions::default()` value and can be
+/// changed with the `with_*` methods.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Converter {
+    opts: ConvertOptions,
+}
+
+impl Converter {
+    /// Creates a converter with the default options.
+    pub fn new() -> Converter {
+        Converter::default()
+    }
+
+    /// Creates a converter from a fully populated `ConvertOptions`.
+    pub fn from_options(opts: ConvertOptions) -> Converter {
+        Converter { opts }
+    }
+
+    /// Returns the options this converter applies.
+    pub fn options(&self) -> &ConvertOptions {
+        &self.opts
+    }
+
+    /// Rewrites relative `.md`/`.mdx` link targets to `.qmd` (enabled by default).
+    pub fn with_link_rewriting(mut self, enabled: bool) -> Converter {
+        self.opts.convert_links = enabled;
+        self
+    }
+
+    /// Copies image folders next to the converted files (enabled by default).
+    pub fn with_image_copy(mut self, enabled: bool) -> Converter {
+        self.opts.copy_images = enabled;
+        self
+    }
+
+    /// Converts `<Tabs>` components to Quarto tabsets (enabled by default).
+    pub fn with_tabs(mut self, enabled: bool) -> Converter {
+        self.opts.convert_tabs = enabled;
+        self
+    }
+
+    /// Includes `.mdx` files when converting a tree (enabled by default).
+    pub fn with_mdx(mut self, enabled: bool) -> Converter {
+        self.opts.include_mdx = enabled;
+        self
+    }
+
+    /// Previews conversions without writing any files.
+    pub fn with_dry_run(mut self, enabled: bool) -> Converter {
+        self.opts.dry_run = enabled;
+        self
+    }
+
+    /// Chooses how admonition titles are carried over to callouts.
+    ///
+    /// ```
+    /// use doc2quarto::{CalloutTitleStyle, Converter};
+    ///
+    /// let converter = Converter::new().with_title_style(CalloutTitleStyle::Attribute);
+    /// assert_eq!(
+    ///     converter.convert_str(":::note Heads up\nBody\n:::\n"),
+    ///     ":::: {.callout-note title=\"Heads up\"}\nBody\n::::\n"
+    /// );
+    /// ```
+    pub fn with_title_style(mut self, style: CalloutTitleStyle) -> Converter {
+        self.opts.callout_title_style = style;
+        self
+    }
+
+    /// Maps a custom admonition name (e.g. `hint`) to a Quarto callout type (e.g. `tip`).
+    ///
+    /// ```
+    /// use doc2quarto::Converter;
+    ///
+    /// let converter = Converter::new().with_admonition_type("hint", "tip");
+    /// assert_eq!(converter.convert_str(":::hint\nBody\n:::"), ":::: {.callout-tip}\nBody\n::::");
+    /// ```
+    pub fn with_admonition_type(mut self, name: &str, callout: &str) -> Converter {
+        self.opts.admonition_types.insert(name.to_lowercase(), callout.to_string());
+        self
+    }
+
+    /// Maps an MDX component to a Quarto markup template, see `convert_components`.
+    ///
+    /// ```
+    /// use doc2quarto::Converter;
+    ///
+    /// let converter = Converter::new().with_component("Kbd", "[{children}]{.kbd}");
+    /// assert_eq!(converter.convert_str("Press <Kbd>Ctrl</Kbd>\n"), "Press [Ctrl]{.kbd}\n");
+    /// ```
+    pub fn with_component(mut self, name: &str, template: &str) -> Converter {
+        self.opts.components.insert(name.to_string(), template.to_string());
+        self
+    }
+
+    /// Registers a custom line transformation, run before the built-in conversions.
+    ///
+    /// Transforms run in registration order on every source line; see
+    /// `LineTransforms` for the exact ordering guarantees.
+    ///
+    /// ```
+    /// use doc2quarto::Converter;
+    ///
+    /// let converter = Converter::new().with_line_transform(|line| {
+    ///     line.strip_prefix("%% ").map(|note| format!(":::note\n{}\n:::", note))
+    /// });
+    /// assert_eq!(converter.convert_str("%% Heads up\n"), ":::: {.callout-note}\nHeads up\n::::\n");
+    /// ```
+    pub fn with_line_transform<F>(mut self, transform: F) -> Converter
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.opts.line_transforms.push(transform);
+        self
+    }
+
+    /// Converts markdown content held in memory.
+    pub fn convert_str(&self, content: &str) -> String {
+        convert_content_with_options(content, &self.opts)
+    }
+
+    /// Converts one file under `source_root` into the mirrored path under `dest_root`.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or the output cannot be written.
+    pub fn convert_file(&self, source_file: &Path, source_root: &Path, dest_root: &Path) -> Result<ConversionReport, ConversionError> {
+        process_files(source_file, source_root, dest_root, &self.opts)
+    }
+
+    /// Converts every source document under `source_root` into `dest_root`.
+    ///
+    /// # Errors
+    /// Returns an error if the tree cannot be walked or a filter pattern is invalid.
+    pub fn convert_tree(&self, source_root: &Path, dest_root: &Path) -> Result<Vec<ConversionReport>, ConversionError> {
+        convert_tree(source_root, dest_root, &self.opts)
+    }
+}
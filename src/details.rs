@@ -0,0 +1,143 @@
+//! Converts raw HTML `<details>`/`<summary>` blocks to Docusaurus `:::details` admonitions.
+//!
+//! ```text
+//! <details>                               :::details[Show the answer]
+//! <summary>Show the answer</summary>  →   42
+//! 42                                      :::
+//! </details>
+//! ```
+//!
+//! The rewritten blocks become collapsed Quarto callouts together with the
+//! regular `:::details` admonitions. The `open` attribute is kept as the
+//! directive attribute `{open}`, which expands the callout (`collapse="false"`).
+
+use crate::fence::FenceTracker;
+use crate::line_map::LineMap;
+use regex::Regex;
+use std::sync::OnceLock;
+
+static DETAILS_OPEN: OnceLock<Regex> = OnceLock::new();
+static SUMMARY: OnceLock<Regex> = OnceLock::new();
+
+const CLOSING_TAG: &str = "</details>";
+
+/// Rewrites every `<details>` HTML block in the content as a `:::details` admonition.
+///
+/// The `<summary>` becomes the admonition's `[label]`, whether it follows the
+/// opening tag on the same line or on a later one. Blocks may be nested, and
+/// lines inside fenced code blocks are never converted.
+///
+/// # Arguments
+/// - `content`: The complete content of the markdown file as a string
+///
+/// # Returns
+/// The content with `<details>` blocks replaced by `:::details` blocks
+pub fn convert_details_html(content: &str) -> String {
+    convert_details_html_mapped(content).0
+}
+
+/// Same as `convert_details_html`, also returning which source line each output line comes from.
+pub(crate) fn convert_details_html_mapped(content: &str) -> (String, LineMap) {
+    let mut result = String::with_capacity(content.len());
+    let mut map = LineMap::default();
+    let mut last_index = 0;
+    let mut fences = FenceTracker::default();
+    // Indentation of every open block, reused for its closing fence
+    let mut open: Vec<String> = Vec::new();
+    // (indentation, `open` attribute) of a `<details>` tag still waiting for its summary
+    let mut pending: Option<(String, bool)> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        // Whatever the previous line wrote comes from it
+        map.track(&result, index.saturating_sub(1));
+        last_index = index;
+        if fences.update(line) {
+            if let Some((indent, expanded)) = pending.take() {
+                open_details(&mut result, &mut open, &indent, expanded, None);
+            }
+            result.push_str(line);
+            result.push('\n');
+            continue;
+        }
+
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let mut rest = line.trim();
+        let mut changed = false;
+
+        if let Some(caps) = details_open().captures(rest) {
+            let expanded = caps.name("attributes").is_some_and(|attributes| attributes.as_str().split_whitespace().any(|a| a == "open"));
+            rest = rest[caps.get(0).map_or(0, |m| m.end())..].trim_start();
+            if let Some((previous_indent, previous_expanded)) = pending.take() {
+                open_details(&mut result, &mut open, &previous_indent, previous_expanded, None);
+            }
+            pending = Some((indent.to_string(), expanded));
+            changed = true;
+        }
+
+        if let Some((details_indent, expanded)) = pending.take() {
+            if rest.is_empty() {
+                // The summary may follow on a later line
+                pending = Some((details_indent, expanded));
+                continue;
+            }
+            let summary = summary().captures(rest).map(|caps| {
+                let summary = caps["summary"].trim().to_string();
+                rest = rest[caps.get(0).map_or(0, |m| m.end())..].trim_start();
+                summary
+            });
+            open_details(&mut result, &mut open, &details_indent, expanded, summary.as_deref());
+            changed = true;
+        }
+
+        let closing = !open.is_empty() && rest.ends_with(CLOSING_TAG);
+        if closing {
+            rest = rest[..rest.len() - CLOSING_TAG.len()].trim_end();
+        }
+
+        if !changed && !closing {
+            result.push_str(line);
+            result.push('\n');
+        } else if !rest.is_empty() {
+            result.push_str(indent);
+            result.push_str(rest);
+            result.push('\n');
+        }
+
+        if closing && let Some(indent) = open.pop() {
+            result.push_str(&indent);
+            result.push_str(":::\n");
+        }
+    }
+
+    if let Some((indent, expanded)) = pending {
+        open_details(&mut result, &mut open, &indent, expanded, None);
+    }
+
+    map.finish(&result, last_index);
+    if !content.ends_with('\n') {
+        result.pop();
+    }
+    (result, map)
+}
+
+/// Emits the `:::details[summary]` line of a block and remembers its indentation.
+fn open_details(result: &mut String, open: &mut Vec<String>, indent: &str, expanded: bool, summary: Option<&str>) {
+    result.push_str(indent);
+    result.push_str(":::details");
+    if let Some(summary) = summary.filter(|summary| !summary.is_empty()) {
+        result.push_str(&format!("[{}]", summary));
+    }
+    if expanded {
+        result.push_str("{open}");
+    }
+    result.push('\n');
+    open.push(indent.to_string());
+}
+
+fn details_open() -> &'static Regex {
+    DETAILS_OPEN.get_or_init(|| Regex::new(r"^<details(?P<attributes>\s[^>]*)?>").unwrap())
+}
+
+fn summary() -> &'static Regex {
+    SUMMARY.get_or_init(|| Regex::new(r"^<summary(?:\s[^>]*)?>(?P<summary>.*?)</summary>").unwrap())
+}
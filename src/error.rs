@@ -0,0 +1,130 @@
+//! Error types returned by the conversion functions and the config loader.
+
+use std::io;
+use std::path::{PathBuf, StripPrefixError};
+use thiserror::Error;
+
+/// Why a file (or a whole tree) could not be converted.
+#[derive(Debug, Error)]
+pub enum ConversionError {
+    /// The source file could not be read
+    #[error("failed to read {path:?}: {source}")]
+    ReadFailed {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    /// The source file is not valid UTF-8 and the policy is to skip it
+    #[error("skipped, file is not valid UTF-8 (invalid byte at offset {offset})")]
+    InvalidUtf8 { path: PathBuf, offset: usize },
+
+    /// The source file does not live under the source root
+    #[error("{path:?} is not inside the source root: {source}")]
+    StripPrefixFailed {
+        path: PathBuf,
+        #[source]
+        source: StripPrefixError,
+    },
+
+    /// The source path has no file name to derive the output name from
+    #[error("source path has no file name: {path:?}")]
+    NoFileName { path: PathBuf },
+
+    /// The converted frontmatter failed `--validate`
+    #[error("{path:?}: converted frontmatter is not valid YAML: {source}")]
+    InvalidFrontmatter {
+        path: PathBuf,
+        #[source]
+        source: serde_yaml::Error,
+    },
+
+    /// A `--authors` file is not a YAML mapping of author slugs
+    #[error("{path:?}: authors file is not a mapping of author slugs: {source}")]
+    InvalidAuthors {
+        path: PathBuf,
+        #[source]
+        source: serde_yaml::Error,
+    },
+
+    /// A destination directory could not be created
+    #[error("failed to create directory {path:?}: {source}")]
+    CreateDirFailed {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    /// A destination file (or its backup, timestamp or the manifest) could not be written or removed
+    #[error("failed to write {path:?}: {source}")]
+    WriteFailed {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    /// The images next to a source file could not be copied
+    #[error("failed to copy images for {path:?}: {source}")]
+    ImageCopyFailed {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    /// The destination path would be written outside the destination root,
+    /// through `..` components or a symlink
+    #[error("refusing to write {path:?}, it resolves outside the destination root {dest_root:?}")]
+    OutsideDestRoot { path: PathBuf, dest_root: PathBuf },
+
+    /// The converted directory is not inside the directory paths are computed relative to
+    #[error("{path:?} is not inside the source root {root:?}")]
+    SourceOutsideRoot { path: PathBuf, root: PathBuf },
+
+    /// The source root of a tree conversion does not exist
+    #[error("source directory does not exist: {path:?}")]
+    SourceNotFound { path: PathBuf },
+
+    /// The output extension is empty or contains a `.` or path separator
+    #[error("invalid output extension {extension:?}, expected a plain extension such as `qmd`")]
+    InvalidExtension { extension: String },
+
+    /// An `--include`/`--exclude` glob is invalid
+    #[error("invalid glob pattern: {0}")]
+    InvalidPattern(#[from] globset::Error),
+
+    /// The source tree could not be watched for changes
+    #[error("failed to watch for changes: {0}")]
+    WatchFailed(#[from] notify::Error),
+}
+
+/// Why a `--config` file could not be loaded.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// The config file could not be read
+    #[error("failed to read config {path:?}: {source}")]
+    ReadFailed {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    /// The config file could not be parsed
+    #[error("invalid config {path:?}: {source}")]
+    InvalidFile {
+        path: PathBuf,
+        #[source]
+        source: Box<ConfigError>,
+    },
+
+    /// The config text is not valid TOML
+    #[error("invalid TOML: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    /// The config text is not valid YAML
+    #[error("invalid YAML: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    /// A setting does not have the expected shape
+    #[error("{0}")]
+    InvalidSetting(String),
+}
@@ -0,0 +1,125 @@
+//! Tracks fenced code blocks so conversion passes can leave code samples untouched.
+
+/// Follows ```` ``` ```` and `~~~` fences line by line.
+///
+/// A fence opens with three or more backticks or tildes (optionally followed by a
+/// language/info string) and closes with a bare fence of the same character that
+/// is at least as long as the opener, so a ```` ```` ```` block can show ```` ``` ````
+/// fences verbatim. Any indentation is accepted because fences nested in list
+/// items are commonly indented past the usual three spaces.
+#[derive(Debug, Default)]
+pub(crate) struct FenceTracker {
+    open: Option<(char, usize)>,
+}
+
+impl FenceTracker {
+    /// Returns true while inside a code block.
+    pub(crate) fn is_open(&self) -> bool {
+        self.open.is_some()
+    }
+
+    /// Feeds the next line and returns true if it belongs to a code block,
+    /// including the opening and closing fence lines themselves.
+    pub(crate) fn update(&mut self, line: &str) -> bool {
+        let marker = fence_marker(line);
+
+        match self.open {
+            Some((ch, len)) => {
+                if let Some((m_ch, m_len, info)) = marker
+                    && m_ch == ch
+                    && m_len >= len
+                    && info.trim().is_empty()
+                {
+                    self.open = None;
+                }
+                true
+            }
+            None => match marker {
+                Some((ch, len, _)) => {
+                    self.open = Some((ch, len));
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+/// Parses a fence line into its character, length and trailing info string.
+pub(crate) fn fence_marker(line: &str) -> Option<(char, usize, &str)> {
+    let rest = line.trim_start();
+    let ch = rest.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = rest.len() - rest.trim_start_matches(ch).len();
+    if len < 3 {
+        return None;
+    }
+    let info = &rest[len..];
+    // Backtick fences may not contain backticks in their info string
+    if ch == '`' && info.contains('`') {
+        return None;
+    }
+    Some((ch, len, info.trim_end_matches(['\n', '\r'])))
+}
+
+/// Splits a line into prose and inline code spans, returned as `(piece, is_code)` in order.
+///
+/// A code span opens with a run of backticks and closes at the next run of the
+/// same length, so ``` ``a `b` c`` ``` is a single span. A run without a matching
+/// close is literal text. Code pieces keep their backticks, and the pieces
+/// joined together give back the line.
+pub(crate) fn split_inline_code(line: &str) -> Vec<(&str, bool)> {
+    let run_at = |start: usize| line[start..].len() - line[start..].trim_start_matches('`').len();
+    let mut pieces = Vec::new();
+    let mut text_start = 0;
+    let mut pos = 0;
+
+    while let Some(found) = line[pos..].find('`') {
+        let open = pos + found;
+        let run = run_at(open);
+        let mut search = open + run;
+        let mut close = None;
+        while let Some(found) = line[search..].find('`') {
+            let start = search + found;
+            let len = run_at(start);
+            if len == run {
+                close = Some(start);
+                break;
+            }
+            search = start + len;
+        }
+
+        match close {
+            Some(close) => {
+                if text_start < open {
+                    pieces.push((&line[text_start..open], false));
+                }
+                pos = close + run;
+                pieces.push((&line[open..pos], true));
+                text_start = pos;
+            }
+            None => pos = open + run,
+        }
+    }
+
+    if text_start < line.len() {
+        pieces.push((&line[text_start..], false));
+    }
+    pieces
+}
+
+/// Rewrites the prose of a line with `f`, leaving inline code spans untouched.
+///
+/// `f` receives the byte offset of each prose piece in the line and its text.
+pub(crate) fn map_outside_inline_code(line: &str, mut f: impl FnMut(usize, &str) -> String) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut offset = 0;
+    for (piece, is_code) in split_inline_code(line) {
+        if is_code {
+            result.push_str(piece);
+        } else {
+            result.push_str(&f(offset, piece));
+        }
+        offset += piece.len();
+    }
+    result
+}
@@ -0,0 +1,44 @@
+//! Glob-based include/exclude filtering of source files.
+//!
+//! Patterns are matched against each file's path relative to the source root,
+//! using `/` separators (e.g. `docs/**`, `blog/**/*.mdx`).
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::Path;
+
+/// Decides which source files are converted based on include/exclude globs.
+#[derive(Debug, Clone)]
+pub struct SourceFilter {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+impl SourceFilter {
+    /// Compiles the include and exclude patterns.
+    ///
+    /// An empty `include` list accepts every file; `exclude` always wins over `include`.
+    ///
+    /// # Errors
+    /// Returns an error naming the first pattern that is not a valid glob.
+    pub fn new<S: AsRef<str>>(include: &[S], exclude: &[S]) -> Result<SourceFilter, globset::Error> {
+        let include = if include.is_empty() { None } else { Some(build_set(include)?) };
+        Ok(SourceFilter {
+            include,
+            exclude: build_set(exclude)?,
+        })
+    }
+
+    /// Returns true if the file at `relative_path` (relative to the source root) should be converted.
+    pub fn matches(&self, relative_path: &Path) -> bool {
+        let included = self.include.as_ref().is_none_or(|set| set.is_match(relative_path));
+        included && !self.exclude.is_match(relative_path)
+    }
+}
+
+fn build_set<S: AsRef<str>>(patterns: &[S]) -> Result<GlobSet, globset::Error> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern.as_ref())?);
+    }
+    builder.build()
+}
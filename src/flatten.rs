@@ -0,0 +1,68 @@
+//! Output naming for `--flatten`, which writes every converted file to the destination root.
+//!
+//! Each file keeps its own name where that is unique. Files whose names collide
+//! are prefixed with their parent directory (`guide/intro.md` → `guide-intro.qmd`),
+//! and further ancestors are added until the names no longer clash.
+
+use log::warn;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Computes the flattened destination path for each source path relative to the source root.
+///
+/// Renamed files and collisions that cannot be resolved are reported as warnings.
+pub(crate) fn flattened_dest_paths(relative_paths: &[&Path], dest_root: &Path, extension: &str) -> Vec<PathBuf> {
+    let parts: Vec<Vec<String>> = relative_paths
+        .iter()
+        .map(|path| {
+            path.with_extension("")
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect()
+        })
+        .collect();
+
+    // Number of trailing path components used in each name, widened while names collide
+    let mut depth = vec![1; parts.len()];
+    let names = loop {
+        let names: Vec<String> = parts
+            .iter()
+            .zip(&depth)
+            .map(|(parts, &depth)| parts[parts.len().saturating_sub(depth)..].join("-"))
+            .collect();
+
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for name in &names {
+            *counts.entry(name).or_default() += 1;
+        }
+
+        let mut widened = false;
+        for (index, name) in names.iter().enumerate() {
+            if counts[name.as_str()] > 1 && depth[index] < parts[index].len() {
+                depth[index] += 1;
+                widened = true;
+            }
+        }
+        if !widened {
+            break names;
+        }
+    };
+
+    let mut seen: BTreeMap<&str, &Path> = BTreeMap::new();
+    for (index, name) in names.iter().enumerate() {
+        if depth[index] > 1 {
+            warn!(
+                "  ⚠ {:?} collides with another file when flattened, writing it as {}.{}",
+                relative_paths[index], name, extension
+            );
+        }
+        if let Some(other) = seen.insert(name, relative_paths[index]) {
+            warn!(
+                "  ⚠ {:?} and {:?} both flatten to {}.{}, the later file overwrites the earlier",
+                other, relative_paths[index], name, extension
+            );
+        }
+    }
+
+    names.into_iter().map(|name| dest_root.join(format!("{}.{}", name, extension))).collect()
+}
@@ -0,0 +1,54 @@
+//! Normalizes legacy remark footnote syntax to the Pandoc footnotes Quarto renders.
+//!
+//! Docusaurus sites written against older remark versions (or `remark-footnotes`
+//! before `inlineNotes`) wrote inline notes as `[^Text of the note.]`: a footnote
+//! "label" holding spaces. Pandoc labels may not contain spaces, so Quarto shows
+//! these literally; they become Pandoc inline notes, `^[Text of the note.]`.
+//!
+//! Standard footnotes are left untouched:
+//!
+//! - references such as `[^1]` or `[^note]`
+//! - definitions such as `[^1]: The note.`
+//! - Pandoc inline notes such as `^[The note.]`
+
+use crate::fence::map_outside_inline_code;
+use regex::Regex;
+use std::sync::OnceLock;
+
+static LEGACY_INLINE_NOTE: OnceLock<Regex> = OnceLock::new();
+
+/// Converts legacy `[^inline note]` footnotes on a single line to Pandoc inline notes.
+///
+/// Only bracket contents with whitespace are inline notes, anything else is a
+/// regular reference. Footnote definitions and inline code spans are never touched.
+///
+/// # Arguments
+/// - `line`: A single line from the markdown file
+///
+/// # Returns
+/// The line with legacy inline notes converted, or unchanged if there are none
+pub fn convert_footnotes(line: &str) -> String {
+    if !line.contains("[^") {
+        return line.to_string();
+    }
+
+    map_outside_inline_code(line, |offset, text| {
+        legacy_inline_note()
+            .replace_all(text, |caps: &regex::Captures| {
+                let note = &caps[1];
+                let Some(whole) = caps.get(0) else { return String::new() };
+                // `[^label with spaces]:` opening a line is a (malformed) definition, not a note
+                let definition = offset == 0 && text[..whole.start()].trim().is_empty() && text[whole.end()..].starts_with(':');
+                if definition || !note.chars().any(char::is_whitespace) {
+                    whole.as_str().to_string()
+                } else {
+                    format!("^[{}]", note.trim())
+                }
+            })
+            .into_owned()
+    })
+}
+
+fn legacy_inline_note() -> &'static Regex {
+    LEGACY_INLINE_NOTE.get_or_init(|| Regex::new(r"\[\^([^\]\[]+)\]").unwrap())
+}
@@ -0,0 +1,432 @@
+//! Converts Docusaurus YAML frontmatter to Quarto frontmatter.
+//!
+//! The frontmatter block is parsed with `serde_yaml` so nested objects, lists
+//! and values containing colons survive the conversion. Field renames are
+//! applied to the parsed mapping, which is then serialized back to YAML.
+//! Callers with their own YAML pipeline can apply the same conversions to an
+//! already parsed mapping with `convert_frontmatter_map`.
+
+use crate::authors::{convert_authors, quarto_author};
+use crate::ConvertOptions;
+use log::warn;
+use serde_yaml::{Mapping, Value};
+
+/// Docusaurus routing and page layout keys that Quarto has no use for.
+pub const DEFAULT_DROPPED_KEYS: &[&str] = &[
+    "hide_title",
+    "pagination_next",
+    "pagination_prev",
+    "pagination_label",
+    "custom_edit_url",
+];
+
+/// How the Docusaurus `id` frontmatter field is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DocId {
+    /// Remove the field, Quarto has no use for it
+    #[default]
+    Drop,
+    /// Replace the field with an `aliases` entry so the id-based URL keeps working
+    Alias,
+    /// Leave the field as-is
+    Keep,
+}
+
+/// Settings for a frontmatter conversion.
+#[derive(Debug, Clone, Copy)]
+pub struct FrontmatterOptions<'a> {
+    /// Default metadata merged into the document without overwriting its keys
+    pub metadata: &'a Mapping,
+    /// How the Docusaurus `id` field is handled
+    pub doc_id: DocId,
+    /// Relative path from the document's directory to the site root (e.g. `../`),
+    /// prepended to root-relative `image` paths
+    pub site_root: &'a str,
+    /// Keys removed from the frontmatter, `DEFAULT_DROPPED_KEYS` unless configured otherwise
+    pub dropped_keys: &'a [&'a str],
+    /// Blog author objects keyed by slug (from `authors.yml`), used to resolve `authors: [slug]`
+    pub authors: &'a Mapping,
+}
+
+/// Converts Docusaurus frontmatter fields to Quarto equivalents.
+///
+/// Currently handles the following conversions:
+/// - `sidebar_position` → `order`
+/// - `sidebar_label` → `title` (only if `title` is not present, otherwise dropped)
+/// - `tags` → `categories` (unless `categories` is already present)
+/// - `slug` → an `aliases` entry so the old Docusaurus URL keeps redirecting
+/// - `id` → removed (see `DocId` for the alternatives)
+/// - `image` → root-relative paths (`/img/card.png`) made relative to the document
+/// - `hide_table_of_contents` → `toc: false` when `true`, removed otherwise (Quarto's default)
+/// - `unlisted: true` → `draft: true` (unless `draft` is set), removed otherwise; `draft` is kept
+/// - `keywords` → kept, a comma-separated string is split into a list
+/// - blog `authors` (slugs or inline objects) → `author` (see the `authors` module),
+///   legacy `author_title`/`author_url` are folded into `author`
+/// - `hide_title`, `pagination_*`, `custom_edit_url` → removed (see `DEFAULT_DROPPED_KEYS`)
+/// - All other fields are preserved as-is
+///
+/// Frontmatter that is not a valid YAML mapping is passed through unchanged
+/// with a warning, rather than risking a lossy conversion.
+///
+/// # Arguments
+/// - `lines`: Slice of string slices representing frontmatter lines (without --- delimiters)
+///
+/// # Returns
+/// A String containing the converted frontmatter (without --- delimiters)
+pub fn convert_frontmatter(lines: &[&str]) -> String {
+    convert_frontmatter_with_metadata(lines, &Mapping::new())
+}
+
+/// Converts Docusaurus frontmatter and merges `metadata` into the result.
+///
+/// Each top-level key of `metadata` (e.g. `format: html`) is added after the
+/// converted fields unless the document already sets that key, so per-file
+/// values always win over the bulk defaults.
+///
+/// # Arguments
+/// - `lines`: Slice of string slices representing frontmatter lines (without --- delimiters)
+/// - `metadata`: Default metadata merged into every document
+///
+/// # Returns
+/// A String containing the converted frontmatter (without --- delimiters)
+pub fn convert_frontmatter_with_metadata(lines: &[&str], metadata: &Mapping) -> String {
+    convert_frontmatter_with_options(lines, &FrontmatterOptions {
+        metadata,
+        doc_id: DocId::default(),
+        site_root: "",
+        dropped_keys: DEFAULT_DROPPED_KEYS,
+        authors: &Mapping::new(),
+    })
+}
+
+/// Converts Docusaurus frontmatter as configured by `opts`.
+///
+/// # Arguments
+/// - `lines`: Slice of string slices representing frontmatter lines (without --- delimiters)
+/// - `opts`: Metadata to merge, how the `id` field is handled and where the site root is
+///
+/// # Returns
+/// A String containing the converted frontmatter (without --- delimiters)
+pub fn convert_frontmatter_with_options(lines: &[&str], opts: &FrontmatterOptions) -> String {
+    let source = lines.join("\n");
+    let mut mapping = if source.trim().is_empty() {
+        Mapping::new()
+    } else {
+        match serde_yaml::from_str::<Mapping>(&source) {
+            Ok(mapping) => mapping,
+            Err(e) => {
+                warn!("  ⚠ Frontmatter is not a valid YAML mapping, leaving it unchanged: {}", e);
+                return format!("{}\n", source);
+            }
+        }
+    };
+
+    convert_frontmatter_map_with_options(&mut mapping, opts);
+    render_mapping(&mapping, &source)
+}
+
+/// Converts an already parsed frontmatter mapping in place, as configured by `opts`.
+///
+/// Applies every field conversion of `convert_frontmatter` and merges
+/// `opts.metadata`, without any YAML parsing or serialization. Root-relative
+/// `image` paths are left as they are, since a bare mapping has no document
+/// location; use `convert_frontmatter_map_with_options` to set `site_root`.
+///
+/// ```
+/// use doc2quarto::{convert_frontmatter_map, ConvertOptions};
+/// use serde_yaml::Mapping;
+///
+/// let mut frontmatter: Mapping = serde_yaml::from_str("sidebar_position: 2\ntags: [rust]").unwrap();
+/// convert_frontmatter_map(&mut frontmatter, &ConvertOptions::default());
+/// assert_eq!(serde_yaml::to_string(&frontmatter).unwrap(), "order: 2\ncategories:\n- rust\n");
+/// ```
+pub fn convert_frontmatter_map(mapping: &mut Mapping, opts: &ConvertOptions) {
+    let dropped_keys: Vec<&str> = opts.dropped_frontmatter_keys.iter().map(String::as_str).collect();
+    convert_frontmatter_map_with_options(mapping, &FrontmatterOptions {
+        metadata: &opts.metadata,
+        doc_id: opts.doc_id,
+        site_root: "",
+        dropped_keys: &dropped_keys,
+        authors: &opts.authors,
+    });
+}
+
+/// Converts an already parsed frontmatter mapping in place, as configured by `opts`.
+///
+/// Each top-level key of `opts.metadata` is added after the converted fields
+/// unless the mapping already sets it.
+pub fn convert_frontmatter_map_with_options(mapping: &mut Mapping, opts: &FrontmatterOptions) {
+    let mut converted = convert_mapping(std::mem::take(mapping), opts);
+    for (key, value) in opts.metadata {
+        if !converted.contains_key(key) {
+            converted.insert(key.clone(), value.clone());
+        }
+    }
+    *mapping = converted;
+}
+
+/// Converts `+++`-delimited TOML frontmatter to Quarto YAML frontmatter.
+///
+/// The TOML table is translated to the equivalent YAML mapping and then goes
+/// through the same field conversions as `convert_frontmatter`. Invalid TOML is
+/// passed through unchanged with a warning.
+///
+/// # Arguments
+/// - `lines`: Slice of string slices representing frontmatter lines (without +++ delimiters)
+///
+/// # Returns
+/// A String containing the converted YAML frontmatter (without delimiters)
+pub fn convert_toml_frontmatter(lines: &[&str]) -> String {
+    convert_toml_frontmatter_with_metadata(lines, &Mapping::new())
+}
+
+/// Converts TOML frontmatter to Quarto YAML and merges `metadata` into the result.
+///
+/// See `convert_frontmatter_with_metadata` for how the metadata is merged.
+pub fn convert_toml_frontmatter_with_metadata(lines: &[&str], metadata: &Mapping) -> String {
+    convert_toml_frontmatter_with_options(lines, &FrontmatterOptions {
+        metadata,
+        doc_id: DocId::default(),
+        site_root: "",
+        dropped_keys: DEFAULT_DROPPED_KEYS,
+        authors: &Mapping::new(),
+    })
+}
+
+/// Converts TOML frontmatter to Quarto YAML as configured by `opts`.
+pub fn convert_toml_frontmatter_with_options(lines: &[&str], opts: &FrontmatterOptions) -> String {
+    let source = lines.join("\n");
+    let mut mapping = if source.trim().is_empty() {
+        Mapping::new()
+    } else {
+        let table = match source.parse::<toml::Table>() {
+            Ok(table) => table,
+            Err(e) => {
+                warn!("  ⚠ Frontmatter is not valid TOML, leaving it unchanged: {}", e);
+                return format!("{}\n", source);
+            }
+        };
+        let Value::Mapping(mapping) = toml_to_yaml(toml::Value::Table(table)) else {
+            return String::new();
+        };
+        mapping
+    };
+
+    convert_frontmatter_map_with_options(&mut mapping, opts);
+    render_mapping(&mapping, &source)
+}
+
+/// Serializes a converted mapping, falling back to the original `source` text if that fails.
+fn render_mapping(converted: &Mapping, source: &str) -> String {
+    if converted.is_empty() {
+        return String::new();
+    }
+
+    serde_yaml::to_string(converted).unwrap_or_else(|_| format!("{}\n", source))
+}
+
+/// Checks that converted frontmatter is a valid YAML mapping.
+///
+/// Frontmatter that could not be converted is passed through unchanged, so
+/// this catches source YAML that was already broken as well as any invalid
+/// output of the conversion itself.
+///
+/// # Arguments
+/// - `frontmatter`: Frontmatter text without the `---` delimiters
+///
+/// # Returns
+/// - `Ok(())` if the text is empty or parses as a YAML mapping
+/// - `Err` with the parser's description of the problem otherwise
+pub fn validate_frontmatter(frontmatter: &str) -> Result<(), serde_yaml::Error> {
+    if frontmatter.trim().is_empty() {
+        return Ok(());
+    }
+    serde_yaml::from_str::<Mapping>(frontmatter).map(|_| ())
+}
+
+/// Returns the frontmatter delimiter (`---` for YAML, `+++` for TOML) if the line is one.
+///
+/// Trailing whitespace, such as a stray `\r` from a Windows editor, is ignored.
+pub(crate) fn frontmatter_delimiter(line: &str) -> Option<&'static str> {
+    match line.trim_end() {
+        "---" => Some("---"),
+        "+++" => Some("+++"),
+        _ => None,
+    }
+}
+
+/// Translates a TOML value into the equivalent YAML value.
+pub(crate) fn toml_to_yaml(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::from(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Value::from(f),
+        toml::Value::Boolean(b) => Value::from(b),
+        // YAML has no native datetime in serde_yaml, keep the RFC 3339 text
+        toml::Value::Datetime(dt) => Value::from(dt.to_string()),
+        toml::Value::Array(items) => Value::Sequence(items.into_iter().map(toml_to_yaml).collect()),
+        toml::Value::Table(table) => Value::Mapping(
+            table
+                .into_iter()
+                .map(|(k, v)| (Value::from(k), toml_to_yaml(v)))
+                .collect(),
+        ),
+    }
+}
+
+/// Applies the Docusaurus → Quarto key conversions to a parsed frontmatter mapping.
+///
+/// Keys keep their original order; renamed keys take the position of the key they replace.
+fn convert_mapping(mapping: Mapping, opts: &FrontmatterOptions) -> Mapping {
+    let doc_id = opts.doc_id;
+    // Quarto has no sidebar label, it can only stand in for a missing title
+    let has_title = mapping.contains_key("title");
+    let has_categories = mapping.contains_key("categories");
+    let has_toc = mapping.contains_key("toc");
+    let has_draft = mapping.contains_key("draft");
+    let has_authors = mapping.contains_key("authors");
+    // Legacy single-author fields that accompany `author: Name`
+    let mut legacy_author = Mapping::new();
+    let mut result = Mapping::with_capacity(mapping.len());
+    let mut slug_alias = None;
+    let mut id_alias = None;
+
+    for (key, value) in mapping {
+        match key.as_str() {
+            // Hiding the table of contents is the inverse of Quarto's `toc`, an explicit `toc` wins
+            Some("hide_table_of_contents") => {
+                if value.as_bool() == Some(true) && !has_toc {
+                    result.insert(Value::from("toc"), Value::from(false));
+                }
+            }
+            // Quarto reads `keywords` as a list, split the comma-separated form some sites use
+            Some("keywords") => {
+                let value = match value {
+                    Value::String(keywords) => Value::Sequence(
+                        keywords
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|keyword| !keyword.is_empty())
+                            .map(Value::from)
+                            .collect(),
+                    ),
+                    other => other,
+                };
+                result.insert(key, value);
+            }
+            // Blog authors become Quarto authors
+            Some("authors") => {
+                result.insert(Value::from("author"), convert_authors(value, opts.authors));
+            }
+            // Docusaurus ignores the legacy `author` when `authors` is set
+            Some("author") if has_authors => {}
+            Some(legacy @ ("author_title" | "author_url" | "author_image_url")) => {
+                let field = legacy.trim_start_matches("author_");
+                legacy_author.insert(Value::from(field), value);
+            }
+            // Quarto has no unlisted pages, a draft is the closest it gets to hiding one
+            Some("unlisted") => {
+                if value.as_bool() == Some(true) && !has_draft {
+                    result.insert(Value::from("draft"), Value::from(true));
+                }
+            }
+            // Routing and layout keys Quarto would only warn about
+            Some(name) if opts.dropped_keys.contains(&name) => {}
+            // Convert 'sidebar_position' to 'order'
+            Some("sidebar_position") => {
+                result.insert(Value::from("order"), value);
+            }
+            // Promote 'sidebar_label' to 'title', or drop it if a title exists
+            Some("sidebar_label") => {
+                if !has_title {
+                    result.insert(Value::from("title"), value);
+                }
+            }
+            // Convert blog 'tags' to Quarto 'categories', keeping the list as-is
+            Some("tags") if !has_categories => {
+                result.insert(Value::from("categories"), value);
+            }
+            // Preserve the Docusaurus URL as a Quarto redirect alias
+            Some("slug") => {
+                slug_alias = value.as_str().and_then(normalize_slug);
+            }
+            // Doc ids only matter to Docusaurus routing
+            Some("id") if doc_id != DocId::Keep => {
+                if doc_id == DocId::Alias {
+                    id_alias = value.as_str().and_then(normalize_slug);
+                }
+            }
+            // Docusaurus serves social cards from the site root, Quarto resolves them from the document
+            Some("image") => {
+                let value = match value.as_str().and_then(|path| site_relative_path(path, opts.site_root)) {
+                    Some(path) => Value::from(path),
+                    None => value,
+                };
+                result.insert(key, value);
+            }
+            _ => {
+                result.insert(key, value);
+            }
+        }
+    }
+
+    if !legacy_author.is_empty()
+        && let Some(name) = result.get("author").filter(|author| author.is_string()).cloned()
+    {
+        let mut author = Mapping::new();
+        author.insert(Value::from("name"), name);
+        author.extend(legacy_author);
+        result.insert(Value::from("author"), quarto_author(&author));
+    }
+
+    // Added last so an existing 'aliases' list is extended rather than replaced
+    for alias in [id_alias, slug_alias].into_iter().flatten() {
+        push_alias(&mut result, alias);
+    }
+
+    result
+}
+
+/// Rewrites a root-relative path (`/img/card.png`) relative to the document.
+///
+/// Returns `None` for paths that are already relative, URLs and protocol-relative
+/// (`//cdn...`) paths, which are kept unchanged.
+fn site_relative_path(path: &str, site_root: &str) -> Option<String> {
+    let rest = path.strip_prefix('/').filter(|rest| !rest.starts_with('/'))?;
+    Some(format!("{}{}", site_root, rest))
+}
+
+/// Normalizes a Docusaurus slug into a Quarto alias path.
+///
+/// Root-relative slugs (`/custom/path`) stay root-relative, while bare or
+/// relative slugs (`my-page`, `./my-page`) are resolved relative to the document,
+/// as Docusaurus does. Trailing slashes are removed. An empty slug yields `None`.
+fn normalize_slug(slug: &str) -> Option<String> {
+    let slug = slug.trim();
+    let alias = match slug.strip_prefix('/') {
+        Some(rest) => format!("/{}", rest.trim_end_matches('/')),
+        None => slug.trim_start_matches("./").trim_end_matches('/').to_string(),
+    };
+
+    if alias.is_empty() { None } else { Some(alias) }
+}
+
+/// Appends an alias to the `aliases` sequence, creating it if needed.
+fn push_alias(mapping: &mut Mapping, alias: String) {
+    let entry = mapping
+        .entry(Value::from("aliases"))
+        .or_insert_with(|| Value::Sequence(Vec::new()));
+
+    match entry {
+        Value::Sequence(aliases) => {
+            if !aliases.iter().any(|a| a.as_str() == Some(alias.as_str())) {
+                aliases.push(Value::from(alias));
+            }
+        }
+        // A single existing alias string becomes a list with both entries
+        other => {
+            let existing = other.clone();
+            *other = Value::Sequence(vec![existing, Value::from(alias)]);
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! Normalizes explicit heading IDs such as `## Title {#custom-id}`.
+//!
+//! Docusaurus and Quarto share the `{#id}` syntax, but Docusaurus is more
+//! lenient about spacing and the characters it accepts in an ID. Pandoc only
+//! accepts letters, digits and `-_:.` in identifiers, so anything else is
+//! replaced with `-`. Classes and key=value attributes are kept.
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+static HEADING_ATTRIBUTES: OnceLock<Regex> = OnceLock::new();
+
+/// Normalizes the trailing attribute block of an ATX heading line.
+///
+/// The ID is sanitized and moved first, and the block is rewritten with single
+/// spaces (`## Title {#my-id .cls key=val}`). Lines that are not headings, or
+/// headings without an attribute block, are returned unchanged.
+///
+/// # Arguments
+/// - `line`: A single line from the markdown file
+///
+/// # Returns
+/// The heading with a normalized attribute block, or the line unchanged
+pub fn convert_heading_attributes(line: &str) -> String {
+    let Some(caps) = heading_attributes().captures(line) else {
+        return line.to_string();
+    };
+
+    let mut id = None;
+    let mut others = Vec::new();
+    for part in caps[3].split_whitespace() {
+        match part.strip_prefix('#') {
+            Some(raw) if id.is_none() => id = Some(sanitize_id(raw)),
+            _ => others.push(part),
+        }
+    }
+
+    let mut attributes: Vec<String> = Vec::new();
+    if let Some(id) = id.filter(|id| !id.is_empty()) {
+        attributes.push(format!("#{}", id));
+    }
+    attributes.extend(others.into_iter().map(str::to_string));
+
+    if attributes.is_empty() {
+        return format!("{}{}", &caps[1], caps[2].trim_end());
+    }
+    format!("{}{} {{{}}}", &caps[1], caps[2].trim_end(), attributes.join(" "))
+}
+
+/// Replaces characters Pandoc does not accept in identifiers with `-`.
+///
+/// Runs of replaced characters collapse to a single `-`, and leading or
+/// trailing dashes are removed.
+pub fn sanitize_id(id: &str) -> String {
+    let mut sanitized = String::with_capacity(id.len());
+    let mut replaced = false;
+    for ch in id.chars() {
+        if ch.is_alphanumeric() || matches!(ch, '-' | '_' | ':' | '.') {
+            sanitized.push(ch);
+            replaced = false;
+        } else if !replaced {
+            sanitized.push('-');
+            replaced = true;
+        }
+    }
+    sanitized.trim_matches('-').to_string()
+}
+
+fn heading_attributes() -> &'static Regex {
+    HEADING_ATTRIBUTES.get_or_init(|| Regex::new(r"^(\s{0,3}#{1,6}\s+)(.*?)\s*\{\s*([#.][^}]*?)\s*\}\s*$").unwrap())
+}
@@ -0,0 +1,180 @@
+//! Rewrites image references so they resolve next to the converted `.qmd` file.
+//!
+//! Image folders (`img`, `images`, ...) are copied next to each converted file,
+//! so a reference such as `![alt](../img/foo.png)` that reaches into a parent
+//! directory's image folder is relocated to `img/foo.png`, and `./img/foo.png`
+//! is normalized to the same form. Both Markdown images and HTML `<img src>`
+//! tags are handled; URLs, root-relative and data URIs are left alone, as are
+//! images shown in inline code.
+//!
+//! A relocated image that would land on a different image the document's own
+//! folder already has (`../img/foo.png` next to `img/foo.png`) is renamed after
+//! the folder it comes from (`img/docs-foo.png`), the way `--flatten` renames
+//! colliding documents.
+
+use crate::fence::{map_outside_inline_code, split_inline_code};
+use log::warn;
+use regex::Regex;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+
+static MARKDOWN_IMAGE: OnceLock<Regex> = OnceLock::new();
+static HTML_IMAGE: OnceLock<Regex> = OnceLock::new();
+
+/// Rewrites the image targets on a single line to their relocated paths.
+///
+/// # Arguments
+/// - `line`: A single line from the markdown file
+/// - `image_folders`: Names of the folders images are copied into
+///
+/// # Returns
+/// The line with image targets rewritten, or unchanged if there are none
+pub fn rewrite_image_references<S: AsRef<str>>(line: &str, image_folders: &[S]) -> String {
+    rewrite_image_references_in(line, image_folders, None)
+}
+
+/// Rewrites image targets like `rewrite_image_references`, renaming the images
+/// that collide with one in `source_dir`, the folder of the document.
+pub(crate) fn rewrite_image_references_in<S: AsRef<str>>(line: &str, image_folders: &[S], source_dir: Option<&Path>) -> String {
+    let relocate = |target: &str| relocated_target(target, image_folders, source_dir).map(|(target, _)| target);
+    map_outside_inline_code(line, |_, text| {
+        let text = markdown_image().replace_all(text, |caps: &regex::Captures| match relocate(&caps[2]) {
+            Some(target) => format!("{}{}{}", &caps[1], target, &caps[3]),
+            None => caps[0].to_string(),
+        });
+
+        html_image()
+            .replace_all(&text, |caps: &regex::Captures| {
+                let (target, quote) = match caps.get(2) {
+                    Some(m) => (m.as_str(), '"'),
+                    None => (caps.get(3).map(|m| m.as_str()).unwrap_or(""), '\''),
+                };
+                match relocate(target) {
+                    Some(target) => format!("{}{}{}{}", &caps[1], quote, target, quote),
+                    None => caps[0].to_string(),
+                }
+            })
+            .into_owned()
+    })
+}
+
+/// Computes where a relative image target lands after the image folders are copied.
+///
+/// The target is normalized lexically and trimmed to start at the first path
+/// component naming one of `image_folders`. Both `/` and `\` count as separators,
+/// the result always uses `/`.
+///
+/// # Returns
+/// - `Some(target)` if the target points into an image folder and needs rewriting
+/// - `None` for URLs, absolute paths, targets outside image folders, or targets
+///   already in their relocated form
+pub fn rewrite_image_target<S: AsRef<str>>(target: &str, image_folders: &[S]) -> Option<String> {
+    if target.starts_with('/') || target.starts_with('#') || target.contains("://") || target.starts_with("data:") {
+        return None;
+    }
+
+    let normalized = normalize_path(Path::new(&target.replace('\\', "/")))?;
+    let components: Vec<&str> = normalized
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => part.to_str(),
+            _ => None,
+        })
+        .collect();
+
+    // Keep the path from the image folder on, its parent directories are not recreated
+    let start = components
+        .iter()
+        .position(|c| image_folders.iter().any(|name| name.as_ref() == *c))?;
+    let relocated = components[start..].join("/");
+
+    if relocated == target { None } else { Some(relocated) }
+}
+
+/// Lists the images referenced from outside the document's own directory.
+///
+/// Each entry pairs the original target (relative to the source file) with the
+/// relocated target (relative to the converted file), so the caller can copy
+/// images that are not already covered by copying the sibling image folders.
+pub fn relocated_images<S: AsRef<str>>(content: &str, image_folders: &[S]) -> Vec<(PathBuf, PathBuf)> {
+    relocated_images_in(content, image_folders, None)
+}
+
+/// Lists relocated images like `relocated_images`, renaming the ones that
+/// collide with an image in `source_dir`, the folder of the document.
+///
+/// Renamed images are reported as warnings.
+pub(crate) fn relocated_images_in<S: AsRef<str>>(content: &str, image_folders: &[S], source_dir: Option<&Path>) -> Vec<(PathBuf, PathBuf)> {
+    let mut images = Vec::new();
+    for line in content.lines() {
+        for (text, _) in split_inline_code(line).into_iter().filter(|(_, is_code)| !is_code) {
+            let markdown = markdown_image().captures_iter(text).map(|c| c.get(2));
+            let html = html_image().captures_iter(text).map(|c| c.get(2).or_else(|| c.get(3)));
+
+            for target in markdown.chain(html).flatten().map(|m| m.as_str()) {
+                if !target.starts_with("..") {
+                    continue;
+                }
+                if let Some((relocated, renamed)) = relocated_target(target, image_folders, source_dir) {
+                    if renamed {
+                        warn!("  ⚠ {:?} collides with another image once copied, writing it as {}", target, relocated);
+                    }
+                    images.push((PathBuf::from(target.replace('\\', "/")), PathBuf::from(relocated)));
+                }
+            }
+        }
+    }
+    images
+}
+
+/// Computes the relocated target like `rewrite_image_target`, and whether it had to be renamed.
+///
+/// An image reached through `..` is renamed when `source_dir` already holds a
+/// different file at its relocated path. The new name is prefixed with the
+/// folder that holds the image's own image folder.
+fn relocated_target<S: AsRef<str>>(target: &str, image_folders: &[S], source_dir: Option<&Path>) -> Option<(String, bool)> {
+    let relocated = rewrite_image_target(target, image_folders)?;
+    let target = target.replace('\\', "/");
+    let Some(source_dir) = source_dir.filter(|_| target.starts_with("..")) else {
+        return Some((relocated, false));
+    };
+
+    let (Ok(own), Ok(referenced)) = (fs::canonicalize(source_dir.join(&relocated)), fs::canonicalize(source_dir.join(&target))) else {
+        return Some((relocated, false));
+    };
+    let holder = referenced.ancestors().nth(relocated.split('/').count()).and_then(Path::file_name).and_then(|name| name.to_str());
+    match (holder, relocated.rsplit_once('/')) {
+        (Some(holder), Some((folder, file_name))) if own != referenced => Some((format!("{}/{}-{}", folder, holder, file_name), true)),
+        _ => Some((relocated, false)),
+    }
+}
+
+/// Collapses `.` and `dir/..` components without touching the filesystem.
+///
+/// Leading `..` components are kept. Returns `None` for paths with a root or prefix.
+fn normalize_path(path: &Path) -> Option<PathBuf> {
+    let mut parts: Vec<Component> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match parts.last() {
+                Some(Component::Normal(_)) => {
+                    parts.pop();
+                }
+                _ => parts.push(component),
+            },
+            Component::Normal(_) => parts.push(component),
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(parts.iter().collect())
+}
+
+fn markdown_image() -> &'static Regex {
+    MARKDOWN_IMAGE.get_or_init(|| Regex::new(r#"(!\[[^\]]*\]\()([^)\s]+)((?:\s+"[^"]*")?\))"#).unwrap())
+}
+
+fn html_image() -> &'static Regex {
+    HTML_IMAGE.get_or_init(|| Regex::new(r#"(<img\b[^>]*?\bsrc=)(?:"([^"]*)"|'([^']*)')"#).unwrap())
+}
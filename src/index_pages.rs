@@ -0,0 +1,53 @@
+//! Output naming for folder landing pages written as `README.md`.
+//!
+//! Docusaurus (like GitHub) shows a folder's `README.md` as its landing page,
+//! while Quarto only treats `index.qmd` that way. `guide/README.md` is therefore
+//! written as `guide/index.qmd`, unless the folder also has an `index.md`: that
+//! collision is reported and the README keeps its own name.
+
+use crate::is_markdown_file;
+use log::warn;
+use std::borrow::Cow;
+use std::fs;
+use std::path::Path;
+
+/// Source file names that already are the landing page of their folder.
+pub(crate) const INDEX_FILES: &[&str] = &["index.md", "index.mdx"];
+
+/// Returns true if the path names a `README.md` (or `.mdx`), in any letter case.
+pub(crate) fn is_readme(path: &Path) -> bool {
+    path.file_stem().is_some_and(|stem| stem.eq_ignore_ascii_case("readme"))
+}
+
+/// Returns true if the directory holds an `index.md`/`index.mdx`.
+pub(crate) fn has_index_file(source_dir: &Path) -> bool {
+    INDEX_FILES.iter().any(|name| source_dir.join(name).is_file())
+}
+
+/// Returns true if the directory holds a folder landing page Quarto would pick up.
+///
+/// With `readme_as_index`, a `README.md` counts as well.
+pub(crate) fn has_index_page(source_dir: &Path, readme_as_index: bool) -> bool {
+    if has_index_file(source_dir) {
+        return true;
+    }
+    readme_as_index
+        && fs::read_dir(source_dir).is_ok_and(|entries| {
+            entries.filter_map(|e| e.ok()).any(|e| is_readme(&e.path()) && is_markdown_file(&e.path(), true) && e.path().is_file())
+        })
+}
+
+/// Returns `relative_path` with a README renamed to `index` if `readme_as_index` is set, keeping its extension.
+///
+/// `source_file` is the README on disk, whose folder is checked for an index
+/// page it would collide with. Other paths are returned unchanged.
+pub(crate) fn landing_page_path<'a>(relative_path: &'a Path, source_file: &Path, readme_as_index: bool) -> Cow<'a, Path> {
+    if !readme_as_index || !is_readme(relative_path) {
+        return Cow::Borrowed(relative_path);
+    }
+    if let Some(index) = INDEX_FILES.iter().map(|name| source_file.with_file_name(name)).find(|index| index.is_file()) {
+        warn!("  ⚠ {:?} and {:?} are both landing pages of their folder, keeping the README name", source_file, index);
+        return Cow::Borrowed(relative_path);
+    }
+    Cow::Owned(relative_path.with_file_name("index").with_extension(relative_path.extension().unwrap_or_default()))
+}
@@ -37,72 +37,998 @@
 //! - Destination directories cannot be created
 //! - Output file cannot be written
 //!
+mod admonition_components;
+mod attributes;
+mod authors;
+mod blog;
+mod category;
+mod code_blocks;
+mod comments;
+mod components;
+mod details;
+mod config;
+mod converter;
+mod error;
+mod fence;
+mod filter;
+mod flatten;
+mod footnotes;
+mod frontmatter;
+mod headings;
+mod index_pages;
+mod images;
+mod line_map;
+mod links;
+mod manifest;
+mod math;
+mod mdx;
+mod tabs;
+mod transform;
+mod versions;
+mod watch;
+mod warning;
+
+pub use admonition_components::convert_admonition_components;
+pub use authors::{convert_authors, load_authors};
+pub use blog::{is_truncate_marker, truncate_summary, TruncateMarker};
+pub use category::{convert_category, CATEGORY_FILES};
+pub use code_blocks::convert_code_fence;
+pub use comments::is_comment_directive;
+pub use components::{convert_components, BUILTIN_COMPONENTS};
+pub use config::Config;
+pub use converter::Converter;
+pub use details::convert_details_html;
+pub use error::{ConfigError, ConversionError};
+pub use filter::SourceFilter;
+pub use footnotes::convert_footnotes;
+pub use frontmatter::{
+    convert_frontmatter, convert_frontmatter_map, convert_frontmatter_map_with_options, convert_frontmatter_with_metadata,
+    convert_frontmatter_with_options,
+    convert_toml_frontmatter, convert_toml_frontmatter_with_metadata, convert_toml_frontmatter_with_options,
+    validate_frontmatter, DocId, FrontmatterOptions, DEFAULT_DROPPED_KEYS,
+};
+pub use headings::{convert_heading_attributes, sanitize_id};
+pub use images::{relocated_images, rewrite_image_references, rewrite_image_target};
+pub use manifest::MANIFEST_FILE;
+pub use links::{
+    convert_links, convert_links_with_extension, find_broken_links, resolve_site_aliases, rewrite_link_target,
+    rewrite_link_target_with_extension, BrokenLink,
+};
+pub use math::convert_math_delimiters;
+pub use mdx::{find_jsx_components, strip_jsx_comments, strip_mdx_statements, ResidualComponent};
+pub use tabs::convert_tabs;
+pub use transform::{LineTransform, LineTransforms};
+pub use versions::{VersionedDocs, VERSIONED_DOCS_DIR};
+pub use warning::{count_warnings, format_warning_counts, Warning, WarningKind};
+pub use watch::{apply_change, watch, WatchUpdate};
+
+use log::{debug, info, warn};
 use regex::Regex;
+use similar::TextDiff;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+use attributes::escape_attribute;
+use manifest::Manifest;
+use line_map::LineMap;
+use walkdir::WalkDir;
+
+// Admonition patterns are compiled once and shared by every line of every file
+static ADMONITION_START: OnceLock<Regex> = OnceLock::new();
+static ADMONITION_END: OnceLock<Regex> = OnceLock::new();
+static PANDOC_DIV_START: OnceLock<Regex> = OnceLock::new();
+
+/// Fence width used for top-level Quarto callouts; each nesting level adds one colon.
+pub const CALLOUT_FENCE_WIDTH: usize = 4;
+
+/// Image folder names copied next to converted files unless configured otherwise.
+pub const DEFAULT_IMAGE_FOLDERS: &[&str] = &["img", "images", "assets", "_assets"];
+
+/// Built-in Docusaurus admonition → Quarto callout type mappings, by callout colour.
+///
+/// Quarto has `note` (blue), `tip` (green), `warning` (amber), `caution`
+/// (orange) and `important` (red). Docusaurus renders `info` blue, `caution`
+/// and `warning` amber (they are aliases) and `danger` red. Custom mappings
+/// in `ConvertOptions::admonition_types` take precedence over these.
+pub const DEFAULT_CALLOUT_TYPES: &[(&str, &str)] = &[
+    ("note", "note"),
+    ("tip", "tip"),
+    ("info", "note"),
+    ("caution", "warning"),
+    ("warning", "warning"),
+    ("danger", "important"),
+    ("details", "note"),
+];
+
+/// Extension given to converted files unless `ConvertOptions::output_extension` says otherwise.
+pub const DEFAULT_OUTPUT_EXTENSION: &str = "qmd";
+
+/// How an admonition title is carried over to the Quarto callout.
+///
+/// Markdown in the title (`:::note **Important** stuff`) is kept as written in
+/// both styles, Quarto renders it as the callout title either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CalloutTitleStyle {
+    /// Emit the title as a `## Title` heading on the line after the fence
+    #[default]
+    Heading,
+    /// Emit the title as a `title="..."` attribute on the fence itself
+    Attribute,
+}
+
+/// What to do with a source file that is not valid UTF-8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidUtf8 {
+    /// Skip the file and record it as failed with an explanatory message
+    #[default]
+    Skip,
+    /// Convert anyway, replacing invalid bytes with U+FFFD and printing a warning
+    Lossy,
+}
+
+/// What to do when the destination `.qmd` file already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExistingOutput {
+    /// Replace the existing file
+    #[default]
+    Overwrite,
+    /// Leave the existing file untouched and report the source as skipped
+    Skip,
+    /// Rename the existing file to `<name>.qmd.bak` before writing
+    Backup,
+}
+
+/// Permissions given to copied image files.
+///
+/// Only applies on Unix; elsewhere copied files keep whatever the platform copy gives them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageMode {
+    /// Keep the source file's permission bits, as `fs::copy` does
+    Keep,
+    /// Set these permission bits, e.g. `0o644`
+    Mode(u32),
+}
+
+impl Default for ImageMode {
+    /// Owner-writable and world-readable (`0o644`), what a web server expects of static assets
+    fn default() -> Self {
+        ImageMode::Mode(0o644)
+    }
+}
+
+/// Summary of converting a single file, returned by `process_files`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversionReport {
+    /// Path of the source markdown file
+    pub source_path: PathBuf,
+    /// Path of the converted .qmd file (computed even in dry-run mode)
+    pub dest_path: PathBuf,
+    /// Number of bytes read from the source file
+    pub bytes_read: usize,
+    /// Number of bytes written to the destination (0 in dry-run mode)
+    pub bytes_written: usize,
+    /// Whether an image folder next to the source was copied
+    pub img_copied: bool,
+    /// Destination paths of the copied image files, in copy order (sorted by path within each folder)
+    pub copied_images: Vec<PathBuf>,
+    /// Number of image files left alone because their copy was already up to date
+    pub images_skipped: usize,
+    /// Whether the file was skipped, because its destination already existed or
+    /// (in incremental mode) its source is unchanged since the last run
+    pub skipped: bool,
+    /// Whether the file was skipped in incremental mode because its source is
+    /// unchanged since the last run (`skipped` is set as well)
+    pub unchanged: bool,
+    /// JSX components still present in the converted output, numbered by their source line
+    pub residual_components: Vec<ResidualComponent>,
+    /// Relative `.qmd` links whose targets do not exist (only with `check_links`)
+    pub broken_links: Vec<BrokenLink>,
+    /// Problems found in the file, residual components and broken links included
+    /// (rewritten links are expected and not reported here)
+    pub warnings: Vec<Warning>,
+    /// Unified diff from the existing destination to the converted content
+    /// (only with `diff`, `None` if nothing would change)
+    pub diff: Option<String>,
+    /// Error message if the file could not be converted
+    pub error: Option<String>,
+}
+
+impl ConversionReport {
+    /// Builds the report for a file whose conversion failed.
+    pub fn failed(source_path: &Path, error: String) -> Self {
+        ConversionReport {
+            source_path: source_path.to_path_buf(),
+            dest_path: PathBuf::new(),
+            bytes_read: 0,
+            bytes_written: 0,
+            img_copied: false,
+            copied_images: Vec::new(),
+            images_skipped: 0,
+            skipped: false,
+            unchanged: false,
+            residual_components: Vec::new(),
+            broken_links: Vec::new(),
+            warnings: Vec::new(),
+            diff: None,
+            error: Some(error),
+        }
+    }
+
+    /// Returns true if the file was converted (or skipped) without error.
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Options controlling how a source tree is converted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConvertOptions {
+    /// Convert `.mdx` files in addition to `.md` files
+    pub include_mdx: bool,
+    /// Report what would be converted without writing any files
+    pub dry_run: bool,
+    /// Rewrite relative `.md`/`.mdx` link targets to `.qmd`
+    pub convert_links: bool,
+    /// Convert `<Tabs>`/`<TabItem>` components to Quarto panel tabsets
+    pub convert_tabs: bool,
+    /// How admonition titles are carried over to callouts
+    pub callout_title_style: CalloutTitleStyle,
+    /// Copy the image folders next to each source file into the destination
+    pub copy_images: bool,
+    /// Names of the folders next to a source file that hold its images
+    pub image_folders: Vec<String>,
+    /// Permissions given to copied image files (Unix only)
+    pub image_mode: ImageMode,
+    /// Leave image copies whose size and modification time match the source alone
+    pub skip_unchanged_images: bool,
+    /// How source files that are not valid UTF-8 are handled
+    pub invalid_utf8: InvalidUtf8,
+    /// How destination files that already exist are handled
+    pub existing_output: ExistingOutput,
+    /// Give each converted file the modification time of its source
+    pub preserve_mtime: bool,
+    /// Write `\r\n` line endings when most lines of the source end that way,
+    /// instead of always writing `\n`
+    pub preserve_line_endings: bool,
+    /// How Docusaurus `<!-- truncate -->` blog markers are handled
+    pub truncate_marker: TruncateMarker,
+    /// Skip files whose content is unchanged since the last run, tracked in a
+    /// manifest in the destination root (tree conversions only)
+    pub incremental: bool,
+    /// Custom admonition → callout type mappings, keyed by lowercase admonition name
+    pub admonition_types: BTreeMap<String, String>,
+    /// Custom MDX component → Quarto markup templates, keyed by component name,
+    /// looked up before `BUILTIN_COMPONENTS`
+    pub components: BTreeMap<String, String>,
+    /// Metadata (e.g. `format: html`) merged into every file's frontmatter without
+    /// overwriting keys the file already sets
+    pub metadata: serde_yaml::Mapping,
+    /// How the Docusaurus `id` frontmatter field is handled
+    pub doc_id: DocId,
+    /// Base path or URL that Docusaurus `@site/` path aliases resolve to
+    pub site_base: Option<String>,
+    /// Re-parse the converted frontmatter and fail the file instead of writing invalid YAML
+    pub validate: bool,
+    /// Directory that destination paths (and the include/exclude globs) are relative
+    /// to, when the converted directory is only part of it, e.g. `docs` when
+    /// converting `docs/guides`; the converted directory itself if `None`
+    pub relative_to: Option<PathBuf>,
+    /// Glob patterns (relative to the source root) a file must match to be converted;
+    /// empty means every file
+    pub include: Vec<String>,
+    /// Glob patterns (relative to the source root) of files to leave out
+    pub exclude: Vec<String>,
+    /// Report relative `.qmd` links whose targets are neither converted in this run nor on disk
+    pub check_links: bool,
+    /// Write every converted file directly into the destination root instead of mirroring the source tree
+    pub flatten: bool,
+    /// Where the `versioned_docs/version-*` folders of a versioned site are written
+    pub versioned_docs: VersionedDocs,
+    /// Compute a diff against the existing destination for each file instead of writing it
+    pub diff: bool,
+    /// Extension (without the dot) of converted files, also used for rewritten doc links
+    pub output_extension: String,
+    /// Frontmatter keys removed from every file, `DEFAULT_DROPPED_KEYS` by default
+    pub dropped_frontmatter_keys: Vec<String>,
+    /// Stop a tree conversion at the first file that fails; files are then
+    /// converted one at a time in path order so the stopping point is predictable
+    pub fail_fast: bool,
+    /// Convert legacy remark `[^inline note]` footnotes to Pandoc inline notes
+    pub normalize_footnotes: bool,
+    /// Convert raw HTML `<details>`/`<summary>` blocks to collapsible callouts
+    pub convert_details: bool,
+    /// Write a folder's `README.md` as `index.qmd`, Quarto's landing page, unless the folder has an `index.md`;
+    /// links to the README follow it
+    pub readme_as_index: bool,
+    /// Custom per-line transformations run before the built-in conversions
+    pub line_transforms: LineTransforms,
+    /// Blog author objects keyed by slug (a Docusaurus `authors.yml`), used to
+    /// resolve `authors: [slug]` frontmatter
+    pub authors: serde_yaml::Mapping,
+}
+
+impl Default for ConvertOptions {
+    fn default() -> Self {
+        ConvertOptions {
+            include_mdx: true,
+            dry_run: false,
+            convert_links: true,
+            convert_tabs: true,
+            callout_title_style: CalloutTitleStyle::default(),
+            copy_images: true,
+            image_folders: DEFAULT_IMAGE_FOLDERS.iter().map(|s| s.to_string()).collect(),
+            image_mode: ImageMode::default(),
+            skip_unchanged_images: true,
+            invalid_utf8: InvalidUtf8::default(),
+            existing_output: ExistingOutput::default(),
+            preserve_mtime: false,
+            preserve_line_endings: false,
+            truncate_marker: TruncateMarker::default(),
+            incremental: false,
+            admonition_types: BTreeMap::new(),
+            components: BTreeMap::new(),
+            metadata: serde_yaml::Mapping::new(),
+            doc_id: DocId::default(),
+            site_base: None,
+            validate: false,
+            relative_to: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            check_links: false,
+            flatten: false,
+            versioned_docs: VersionedDocs::default(),
+            diff: false,
+            output_extension: DEFAULT_OUTPUT_EXTENSION.to_string(),
+            dropped_frontmatter_keys: DEFAULT_DROPPED_KEYS.iter().map(|s| s.to_string()).collect(),
+            fail_fast: false,
+            normalize_footnotes: false,
+            convert_details: false,
+            readme_as_index: true,
+            line_transforms: LineTransforms::default(),
+            authors: serde_yaml::Mapping::new(),
+        }
+    }
+}
 
 
 /// Processes a single markdown file from Docusaurus to Quarto format.
 /// This function handles the complete conversion pipeline for a single file:
 /// - Reads the source markdown file
 /// - Converts content (frontmatter and admonitions)
-/// - Preserves directory structure in destination
-/// - Changes file extension from .md to .qmd
-/// - Copies associated img folders
+/// - Preserves directory structure in destination (unless `opts.flatten` is set,
+///   which writes the file directly into `dest_root`; only `convert_tree`
+///   disambiguates flattened names that collide)
+/// - Changes file extension from .md/.mdx to .qmd (or `opts.output_extension`)
+/// - Copies associated image folders (`img`, `images`, ... as configured in `opts`)
 ///
 /// # Arguments
-/// - `source_file`: Path to the source .md file
+/// - `source_file`: Path to the source .md or .mdx file
 /// - `source_root`: Root directory of the source files (for calculating relative paths)
 /// - `dest_root`: Root directory where converted files will be written
+/// - `opts`: Conversion options; in dry-run mode the destination path and converted
+///   size are reported but nothing is written
+///
+/// # Returns
+/// - `Ok(ConversionReport)` describing the conversion on success
+/// - `Err` if file reading, path manipulation, or writing fails, `opts.output_extension`
+///   is invalid, or the destination would land outside `dest_root`
+///
+pub fn process_files(source_file: &Path, source_root: &Path, dest_root: &Path, opts: &ConvertOptions) -> Result<ConversionReport, ConversionError> {
+    validate_output_extension(&opts.output_extension)?;
+
+    // Calculate the relative path from source root
+    let relative_path = strip_source_root(source_file, source_root)?;
+    debug!("  📍 Relative path: {:?}", relative_path);
+
+    let relative_path = index_pages::landing_page_path(relative_path, source_file, opts.readme_as_index);
+    let relative_path = versions::output_relative_path(&relative_path, opts.versioned_docs);
+    let dest_path = single_dest_path(&relative_path, dest_root, opts.flatten, &opts.output_extension);
+    ensure_within_dest_root(&dest_path, dest_root)?;
+    convert_file_to(source_file, &dest_path, dest_root, opts, &BTreeSet::new())
+}
+
+/// Returns the relative path from `dest_path`'s directory up to `dest_root`, e.g. `../../`.
+fn site_root_prefix(dest_path: &Path, dest_root: &Path) -> String {
+    let depth = dest_path
+        .strip_prefix(dest_root)
+        .map_or(0, |relative| relative.components().count().saturating_sub(1));
+    "../".repeat(depth)
+}
+
+/// Checks that an output extension is a plain file extension such as `qmd` or `Rmd`.
+///
+/// # Returns
+/// - `Ok(())` if the extension is non-empty and contains no `.` or path separator
+/// - `Err(ConversionError::InvalidExtension)` otherwise
+pub fn validate_output_extension(extension: &str) -> Result<(), ConversionError> {
+    if extension.is_empty() || extension.contains(['/', '\\', '.']) {
+        return Err(ConversionError::InvalidExtension { extension: extension.to_string() });
+    }
+    Ok(())
+}
+
+/// Destination of one file converted on its own, without the collision handling of `plan_dest_paths`.
+fn single_dest_path(relative_path: &Path, dest_root: &Path, flatten: bool, extension: &str) -> PathBuf {
+    // Create destination path with the output extension, directly in the root when flattening
+    let mut dest_path = if flatten {
+        dest_root.join(relative_path.file_name().unwrap_or_default())
+    } else {
+        dest_root.join(relative_path)
+    };
+    dest_path.set_extension(extension);
+    dest_path
+}
+
+/// Checks that `dest_path` cannot be written outside `dest_root`.
+///
+/// The path below `dest_root` may only hold plain names, no `..` or root
+/// components. When `dest_root` exists, the deepest existing part of
+/// `dest_path` is also canonicalized, so a symlinked directory (or file)
+/// pointing out of the tree is caught before anything is written through it.
+fn ensure_within_dest_root(dest_path: &Path, dest_root: &Path) -> Result<(), ConversionError> {
+    let outside = || ConversionError::OutsideDestRoot { path: dest_path.to_path_buf(), dest_root: dest_root.to_path_buf() };
+
+    let relative = dest_path.strip_prefix(dest_root).map_err(|_| outside())?;
+    if !relative.components().all(|component| matches!(component, Component::Normal(_))) {
+        return Err(outside());
+    }
+
+    if let Ok(root) = dest_root.canonicalize()
+        && let Some(existing) = dest_path.ancestors().find(|path| path.exists())
+        && let Ok(resolved) = existing.canonicalize()
+        && !resolved.starts_with(&root)
+    {
+        return Err(outside());
+    }
+    Ok(())
+}
+
+/// Returns `source_file` relative to `source_root`.
+fn strip_source_root<'a>(source_file: &'a Path, source_root: &Path) -> Result<&'a Path, ConversionError> {
+    source_file
+        .strip_prefix(source_root)
+        .map_err(|source| ConversionError::StripPrefixFailed { path: source_file.to_path_buf(), source })
+}
+
+/// Computes the destination path of every source file in a tree conversion.
+///
+/// Paths mirror the source tree (with versioned docs placed per `opts.versioned_docs`
+/// and README landing pages renamed per `opts.readme_as_index`), or with
+/// `opts.flatten` all land in `dest_root` with colliding names disambiguated
+/// by their parent directories.
+fn plan_dest_paths(source_files: &[PathBuf], relative_paths: &[&Path], dest_root: &Path, opts: &ConvertOptions) -> Vec<PathBuf> {
+    let output_paths: Vec<Cow<Path>> = relative_paths
+        .iter()
+        .zip(source_files)
+        .map(|(relative_path, source_file)| index_pages::landing_page_path(relative_path, source_file, opts.readme_as_index))
+        .collect();
+    let output_paths: Vec<&Path> = output_paths.iter().map(AsRef::as_ref).collect();
+
+    if opts.flatten {
+        return flatten::flattened_dest_paths(&output_paths, dest_root, &opts.output_extension);
+    }
+    output_paths
+        .iter()
+        .map(|output_path| dest_root.join(versions::output_relative_path(output_path, opts.versioned_docs)).with_extension(&opts.output_extension))
+        .collect()
+}
+
+/// Computes the destination of one source file of a tree the way `plan_dest_paths` does for the whole run.
+///
+/// `--flatten` names depend on the other files of the tree, which are then
+/// collected from `source_dir`. `source_file` may no longer exist.
+pub(crate) fn planned_dest_path(source_file: &Path, source_dir: &Path, source_root: &Path, dest_root: &Path, opts: &ConvertOptions) -> Result<PathBuf, ConversionError> {
+    let mut source_files = if opts.flatten { collect_filtered_files(source_dir, source_root, opts)?.0 } else { Vec::new() };
+    let index = match source_files.iter().position(|file| file == source_file) {
+        Some(index) => index,
+        None => {
+            source_files.push(source_file.to_path_buf());
+            source_files.len() - 1
+        }
+    };
+    let relative_paths = source_files
+        .iter()
+        .map(|file| strip_source_root(file, source_root))
+        .collect::<Result<Vec<&Path>, _>>()?;
+    Ok(plan_dest_paths(&source_files, &relative_paths, dest_root, opts).swap_remove(index))
+}
+
+/// Converts a source file of a tree to its planned destination, see `planned_dest_path`.
+pub(crate) fn process_planned(source_file: &Path, dest_path: &Path, dest_root: &Path, opts: &ConvertOptions) -> Result<ConversionReport, ConversionError> {
+    validate_output_extension(&opts.output_extension)?;
+    ensure_within_dest_root(dest_path, dest_root)?;
+    convert_file_to(source_file, dest_path, dest_root, opts, &BTreeSet::new())
+}
+
+/// Converts a single markdown file outside of a directory tree conversion.
+///
+/// `dest` is treated as the output file when it has an extension and is not an
+/// existing directory (e.g. `out/intro.qmd`); otherwise it is a directory and
+/// the output is written to `dest/<name>.qmd` (or `opts.output_extension`), a
+/// `README.md` becoming `dest/index.qmd` per `opts.readme_as_index`.
+///
+/// # Arguments
+/// - `source_file`: Path to the source .md or .mdx file
+/// - `dest`: Output file or directory
+/// - `opts`: Conversion options
 ///
 /// # Returns
-/// - `Ok(())` on successful conversion and write
-/// - `Err` if file reading, path manipulation, or writing fails
+/// - `Ok(ConversionReport)` describing the conversion on success
+/// - `Err` if file reading or writing fails, or `opts.output_extension` is invalid
+pub fn convert_file(source_file: &Path, dest: &Path, opts: &ConvertOptions) -> Result<ConversionReport, ConversionError> {
+    validate_output_extension(&opts.output_extension)?;
+    let dest_path = file_dest_path(source_file, dest, opts)?;
+
+    // The output directory stands in for the site root
+    let dest_root = dest_path.parent().unwrap_or(Path::new(""));
+    convert_file_to(source_file, &dest_path, dest_root, opts, &BTreeSet::new())
+}
+
+/// Destination of a file converted with `convert_file`, `dest` being the output file or its directory.
+fn file_dest_path(source_file: &Path, dest: &Path, opts: &ConvertOptions) -> Result<PathBuf, ConversionError> {
+    if dest.extension().is_some() && !dest.is_dir() {
+        return Ok(dest.to_path_buf());
+    }
+    let file_name = source_file
+        .file_name()
+        .ok_or_else(|| ConversionError::NoFileName { path: source_file.to_path_buf() })?;
+    let file_name = index_pages::landing_page_path(Path::new(file_name), source_file, opts.readme_as_index);
+    Ok(dest.join(file_name).with_extension(&opts.output_extension))
+}
+
+
+/// Reads, converts and writes one file to an already computed destination path.
 ///
-pub fn process_files(source_file: &Path, source_root: &Path, dest_root: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    // Read the entire file  content as a String
-    let content = fs::read_to_string(source_file)?;
-    println!("  📖 Read {} bytes from {:?}", content.len(), source_file);
+/// `dest_root` is the root of the output tree, which root-relative frontmatter
+/// paths are resolved against. `known_outputs` holds the normalized destination
+/// paths of the whole run and is only consulted when `opts.check_links` is set.
+fn convert_file_to(source_file: &Path, dest_path: &Path, dest_root: &Path, opts: &ConvertOptions, known_outputs: &BTreeSet<PathBuf>) -> Result<ConversionReport, ConversionError> {
+    // Read the entire file content as a String
+    let content = read_source(source_file, opts.invalid_utf8)?;
+    debug!("  📖 Read {} bytes from {:?}", content.len(), source_file);
 
     // Convert the content from Docusaurus to Quarto format
-    let converted = convert_content(&content);
-    println!("  🔄 Converted content: {} bytes", converted.len());
+    let site_root = site_root_prefix(dest_path, dest_root);
+    let ConvertedContent { content: converted, frontmatter, warnings, residual_components } = convert_content_inner(&content, opts, &site_root, source_file.parent());
+    debug!("  🔄 Converted content: {} bytes", converted.len());
 
-    // Calculate the relative path from source root
-    let relative_path = source_file.strip_prefix(source_root)?;
-    println!("  📍 Relative path: {:?}", relative_path);
+    // Refuse to write output whose frontmatter Quarto could not parse
+    if opts.validate
+        && let Some(frontmatter) = &frontmatter
+        && let Err(e) = validate_frontmatter(frontmatter)
+    {
+        return Err(ConversionError::InvalidFrontmatter { path: source_file.to_path_buf(), source: e });
+    }
+    debug!("  📝 Destination path: {:?}", dest_path);
+
+    let mut report = ConversionReport {
+        source_path: source_file.to_path_buf(),
+        dest_path: dest_path.to_path_buf(),
+        bytes_read: content.len(),
+        bytes_written: 0,
+        img_copied: false,
+        copied_images: Vec::new(),
+        images_skipped: 0,
+        skipped: false,
+        unchanged: false,
+        residual_components,
+        broken_links: Vec::new(),
+        warnings: warnings.into_iter().filter(|w| w.kind != WarningKind::RewrittenLink).collect(),
+        diff: None,
+        error: None,
+    };
+
+    for component in &report.residual_components {
+        push_warning(&mut report.warnings, residual_component_warning(component));
+    }
+
+    if opts.check_links {
+        report.broken_links = find_broken_links(&converted, dest_path, known_outputs);
+        for link in &report.broken_links {
+            push_warning(&mut report.warnings, Warning::new(
+                Some(link.line),
+                WarningKind::BrokenLink,
+                format!("link target `{}` does not exist in the output", link.target),
+            ));
+        }
+    }
+
+    // Never clobber an existing output when asked not to
+    let exists = dest_path.exists();
+    if exists && opts.existing_output == ExistingOutput::Skip {
+        info!("  ⏭ Skipped, {:?} already exists", dest_path);
+        report.skipped = true;
+        return Ok(report);
+    }
 
-    // Create destination path with .qmd extension
-    let mut dest_path = dest_root.join(relative_path);
-    dest_path.set_extension("qmd");
-    println!("  📝 Destination path: {:?}", dest_path);
+    // Show what writing the file would change; diff mode never writes
+    if opts.diff {
+        let existing = if exists { fs::read_to_string(dest_path).unwrap_or_default() } else { String::new() };
+        if existing != converted {
+            let name = dest_path.display().to_string();
+            let diff = TextDiff::from_lines(&existing, &converted)
+                .unified_diff()
+                .header(&name, &name)
+                .to_string();
+            report.diff = Some(diff);
+        }
+        return Ok(report);
+    }
+
+    // In dry-run mode stop before touching the filesystem
+    if opts.dry_run {
+        info!("  🔍 Dry run: would write {} bytes to {:?}", converted.len(), dest_path);
+        return Ok(report);
+    }
 
     // Create parent directories if they don't exist
     if let Some(parent) = dest_path.parent() {
-        fs::create_dir_all(parent)?;
-        println!("  📁 Created parent directory: {:?}", parent);
+        fs::create_dir_all(parent).map_err(|source| ConversionError::CreateDirFailed { path: parent.to_path_buf(), source })?;
+        debug!("  📁 Created parent directory: {:?}", parent);
+    }
+
+    // Keep the previous output around before replacing it
+    if exists && opts.existing_output == ExistingOutput::Backup {
+        let mut backup = dest_path.as_os_str().to_owned();
+        backup.push(".bak");
+        fs::rename(dest_path, &backup).map_err(|source| ConversionError::WriteFailed { path: PathBuf::from(&backup), source })?;
+        info!("  💾 Backed up existing file to {:?}", backup);
     }
 
     // Write converted content to destination file
-    fs::write(&dest_path, converted)?;
-    println!("  ✅ Written to: {:?}", dest_path);
+    let write_failed = |source| ConversionError::WriteFailed { path: dest_path.to_path_buf(), source };
+    fs::write(dest_path, &converted).map_err(write_failed)?;
+    report.bytes_written = converted.len();
+    info!("  ✅ Written to: {:?}", dest_path);
+
+    // Carry the source mtime over so make-style tools can compare source and output
+    if opts.preserve_mtime {
+        let modified = fs::metadata(source_file)
+            .and_then(|metadata| metadata.modified())
+            .map_err(|source| ConversionError::ReadFailed { path: source_file.to_path_buf(), source })?;
+        fs::File::options()
+            .write(true)
+            .open(dest_path)
+            .and_then(|file| file.set_modified(modified))
+            .map_err(write_failed)?;
+    }
 
-    // Copy img folder if it exists in the same directory
-    copy_img_folder(source_file, &dest_path)?;
+    // Copy image folders if they exist in the same directory
+    if opts.copy_images {
+        let copy_failed = |source| ConversionError::ImageCopyFailed { path: source_file.to_path_buf(), source };
+        let mut copier = ImageCopier::new(opts.image_mode, opts.skip_unchanged_images);
+        report.img_copied = copy_image_folders_to(source_file, dest_path, &opts.image_folders, &mut copier).map_err(copy_failed)?;
+        report.img_copied |= copy_relocated_images(&content, source_file, dest_path, &opts.image_folders, &mut copier).map_err(copy_failed)?;
+        report.copied_images = copier.copied;
+        report.images_skipped = copier.skipped;
+    }
 
 
-    Ok(())
+    Ok(report)
+}
+
+
+/// Reads a source file, applying `policy` if it is not valid UTF-8.
+fn read_source(source_file: &Path, policy: InvalidUtf8) -> Result<String, ConversionError> {
+    let bytes = fs::read(source_file).map_err(|source| ConversionError::ReadFailed { path: source_file.to_path_buf(), source })?;
+    match String::from_utf8(bytes) {
+        Ok(content) => Ok(content),
+        Err(e) => match policy {
+            InvalidUtf8::Skip => Err(ConversionError::InvalidUtf8 {
+                path: source_file.to_path_buf(),
+                offset: e.utf8_error().valid_up_to(),
+            }),
+            InvalidUtf8::Lossy => {
+                warn!(
+                    "  ⚠ {:?} is not valid UTF-8, replacing invalid bytes with U+FFFD",
+                    source_file
+                );
+                Ok(String::from_utf8_lossy(e.as_bytes()).into_owned())
+            }
+        },
+    }
+}
+
+
+/// Converts every Docusaurus document under `source_root` into `dest_root`.
+///
+/// Walks the source tree recursively, converts each `.md` (and `.mdx`, unless
+/// disabled in `opts`) file with `process_files` and returns one report per file
+/// in sorted path order. Files are converted in parallel; a failing file does not
+/// stop the run, it is recorded as a failed report instead.
+///
+/// # Arguments
+/// - `source_root`: Root directory of the Docusaurus docs
+/// - `dest_root`: Root directory where converted files will be written
+/// - `opts`: Conversion options
+///
+/// # Returns
+/// - `Ok(Vec<ConversionReport>)` with one report per discovered file
+/// - `Err` if the source root does not exist
+pub fn convert_tree(source_root: &Path, dest_root: &Path, opts: &ConvertOptions) -> Result<Vec<ConversionReport>, ConversionError> {
+    convert_tree_with_progress(source_root, dest_root, opts, |_| {})
+}
+
+/// Same as `convert_tree`, calling `on_file` with each report as soon as its file is done.
+///
+/// The callback runs on worker threads, in completion order rather than path order.
+/// With `opts.fail_fast` the files are converted sequentially and the returned
+/// reports end with the first failure, later files are not converted.
+///
+/// With `opts.relative_to` set, only the files under `source_root` are converted
+/// but their destinations mirror their paths below `opts.relative_to`, which has
+/// to contain `source_root`.
+pub fn convert_tree_with_progress<F>(source_root: &Path, dest_root: &Path, opts: &ConvertOptions, on_file: F) -> Result<Vec<ConversionReport>, ConversionError>
+where
+    F: Fn(&ConversionReport) + Sync,
+{
+    use rayon::prelude::*;
+
+    if !source_root.exists() {
+        return Err(ConversionError::SourceNotFound { path: source_root.to_path_buf() });
+    }
+    validate_output_extension(&opts.output_extension)?;
+    let (source_dir, source_root) = resolve_relative_root(source_root, opts)?;
+    let source_root: &Path = &source_root;
+
+    let manifest = if opts.incremental { Some(Manifest::load(dest_root)) } else { None };
+
+    let (source_files, _) = collect_filtered_files(&source_dir, source_root, opts)?;
+    let relative_paths = source_files
+        .iter()
+        .map(|file| strip_source_root(file, source_root))
+        .collect::<Result<Vec<&Path>, _>>()?;
+    let dest_paths = plan_dest_paths(&source_files, &relative_paths, dest_root, opts);
+
+    // Links may point at files converted later in the run, so resolve against the planned outputs
+    let known_outputs: BTreeSet<PathBuf> = if opts.check_links {
+        dest_paths.iter().map(|path| links::normalize_path(path)).collect()
+    } else {
+        BTreeSet::new()
+    };
+
+    let convert_one = |(source_file, dest_path): (&PathBuf, &PathBuf)| {
+        let result = ensure_within_dest_root(dest_path, dest_root).and_then(|()| match &manifest {
+            Some(manifest) => process_incremental(source_file, source_root, dest_root, dest_path, opts, manifest, &known_outputs),
+            None => convert_file_to(source_file, dest_path, dest_root, opts, &known_outputs).map(|report| (report, None)),
+        });
+        let (report, entry) = result.unwrap_or_else(|e| (ConversionReport::failed(source_file, e.to_string()), None));
+        on_file(&report);
+        (report, entry)
+    };
+
+    let results: Vec<(ConversionReport, Option<ManifestEntry>)> = if opts.fail_fast {
+        let mut results = Vec::new();
+        for pair in source_files.iter().zip(&dest_paths) {
+            let result = convert_one(pair);
+            let failed = !result.0.is_success();
+            results.push(result);
+            if failed {
+                break;
+            }
+        }
+        results
+    } else {
+        source_files.par_iter().zip(&dest_paths).map(convert_one).collect()
+    };
+
+    // Sidebar categories become the index pages Quarto takes section titles from
+    category::write_section_pages(source_root, dest_root, &dest_paths, opts);
+
+    if opts.versioned_docs == VersionedDocs::Profiles && !opts.flatten && !opts.dry_run && !opts.diff {
+        versions::write_profiles(&relative_paths, dest_root, &opts.output_extension, opts.existing_output);
+    }
+
+    let mut reports = Vec::with_capacity(results.len());
+    let mut manifest = manifest;
+    for (report, entry) in results {
+        if let (Some(manifest), Some((key, hash))) = (manifest.as_mut(), entry) {
+            manifest.insert(key, hash);
+        }
+        reports.push(report);
+    }
+
+    if let Some(manifest) = manifest
+        && !opts.dry_run
+        && !opts.diff
+    {
+        manifest
+            .save(dest_root)
+            .map_err(|source| ConversionError::WriteFailed { path: dest_root.join(MANIFEST_FILE), source })?;
+    }
+
+    Ok(reports)
+}
+
+/// Lists the files a conversion would convert and their destinations, without reading or writing any of them.
+///
+/// `source` is either a directory, planned as by `convert_tree` (filters,
+/// `opts.relative_to`, flattening and versioned docs included), or a single
+/// file, placed as by `convert_file`, or as by `process_files` below
+/// `opts.relative_to` when it is set.
+///
+/// # Returns
+/// - `Ok(Vec<(source, dest)>)` in path order
+/// - `Err` if the source does not exist, a glob or the output extension is
+///   invalid, or the source is outside `opts.relative_to`
+pub fn plan_conversion(source: &Path, dest: &Path, opts: &ConvertOptions) -> Result<Vec<(PathBuf, PathBuf)>, ConversionError> {
+    if !source.exists() {
+        return Err(ConversionError::SourceNotFound { path: source.to_path_buf() });
+    }
+    validate_output_extension(&opts.output_extension)?;
+
+    if source.is_file() {
+        let dest_path = match &opts.relative_to {
+            Some(root) => {
+                let relative_path = index_pages::landing_page_path(strip_source_root(source, root)?, source, opts.readme_as_index);
+                let relative_path = versions::output_relative_path(&relative_path, opts.versioned_docs);
+                single_dest_path(&relative_path, dest, opts.flatten, &opts.output_extension)
+            }
+            None => file_dest_path(source, dest, opts)?,
+        };
+        return Ok(vec![(source.to_path_buf(), dest_path)]);
+    }
+
+    let (source_dir, source_root) = resolve_relative_root(source, opts)?;
+    let (source_files, _) = collect_filtered_files(&source_dir, &source_root, opts)?;
+    let relative_paths = source_files
+        .iter()
+        .map(|file| strip_source_root(file, &source_root))
+        .collect::<Result<Vec<&Path>, _>>()?;
+    let dest_paths = plan_dest_paths(&source_files, &relative_paths, dest, opts);
+    Ok(source_files.iter().cloned().zip(dest_paths).collect())
+}
+
+/// Manifest key and content hash recorded for a converted file.
+type ManifestEntry = (String, String);
+
+/// Converts a file unless the manifest shows its content is unchanged and its output exists.
+///
+/// Returns the report along with the manifest entry to record for the file.
+fn process_incremental(source_file: &Path, source_root: &Path, dest_root: &Path, dest_path: &Path, opts: &ConvertOptions, manifest: &Manifest, known_outputs: &BTreeSet<PathBuf>) -> Result<(ConversionReport, Option<ManifestEntry>), ConversionError> {
+    let relative_path = strip_source_root(source_file, source_root)?;
+    let key = manifest::manifest_key(relative_path);
+    let bytes = fs::read(source_file).map_err(|source| ConversionError::ReadFailed { path: source_file.to_path_buf(), source })?;
+    let hash = manifest::content_hash(&bytes);
+
+    if manifest.is_unchanged(&key, &hash) && dest_path.exists() {
+        info!("  ⏭ Unchanged since last run: {:?}", source_file);
+        let report = ConversionReport {
+            source_path: source_file.to_path_buf(),
+            dest_path: dest_path.to_path_buf(),
+            bytes_read: 0,
+            bytes_written: 0,
+            img_copied: false,
+            copied_images: Vec::new(),
+            images_skipped: 0,
+            skipped: true,
+            unchanged: true,
+            residual_components: Vec::new(),
+            broken_links: Vec::new(),
+            warnings: Vec::new(),
+            diff: None,
+            error: None,
+        };
+        return Ok((report, Some((key, hash))));
+    }
+
+    let report = convert_file_to(source_file, dest_path, dest_root, opts, known_outputs)?;
+    // A file skipped by --no-clobber was not converted, so it stays out of date
+    let entry = if report.skipped { None } else { Some((key, hash)) };
+    Ok((report, entry))
+}
+
+/// Recursively collects the Docusaurus documents under `source_root`, sorted by path.
+///
+/// # Arguments
+/// - `source_root`: Directory to walk
+/// - `include_mdx`: Whether `.mdx` files should be collected alongside `.md` files
+pub fn collect_source_files(source_root: &Path, include_mdx: bool) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = WalkDir::new(source_root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && is_markdown_file(e.path(), include_mdx))
+        .map(|e| e.path().to_path_buf())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Collects the source documents under `source_root` that pass the include/exclude globs in `opts`.
+///
+/// With `opts.relative_to` set, the globs are matched against paths relative to that directory instead.
+///
+/// # Returns
+/// - `Ok((files, filtered_out))` with the sorted files to convert and the number
+///   of documents left out by the filters
+/// - `Err` if one of the glob patterns is invalid, or `source_root` is outside `opts.relative_to`
+pub fn collect_filtered_source_files(source_root: &Path, opts: &ConvertOptions) -> Result<(Vec<PathBuf>, usize), ConversionError> {
+    let (source_dir, source_root) = resolve_relative_root(source_root, opts)?;
+    collect_filtered_files(&source_dir, &source_root, opts)
+}
+
+fn collect_filtered_files(source_dir: &Path, source_root: &Path, opts: &ConvertOptions) -> Result<(Vec<PathBuf>, usize), ConversionError> {
+    let filter = SourceFilter::new(&opts.include, &opts.exclude)?;
+    let (files, filtered_out): (Vec<PathBuf>, Vec<PathBuf>) = collect_source_files(source_dir, opts.include_mdx)
+        .into_iter()
+        .partition(|file| filter.matches(file.strip_prefix(source_root).unwrap_or(file)));
+
+    Ok((files, filtered_out.len()))
+}
+
+/// Returns the directory to scan and the root that paths are computed relative to.
+///
+/// Both are `source_dir` unless `opts.relative_to` names another root. The
+/// paths are canonicalized if needed to tell whether `source_dir` lies inside
+/// the root (`./docs/guides` under `docs`), so files found under the returned
+/// directory always start with the returned root.
+///
+/// # Errors
+/// `ConversionError::SourceOutsideRoot` if `source_dir` is not inside `opts.relative_to`.
+pub(crate) fn resolve_relative_root<'a>(source_dir: &'a Path, opts: &'a ConvertOptions) -> Result<(Cow<'a, Path>, Cow<'a, Path>), ConversionError> {
+    let Some(root) = opts.relative_to.as_deref() else {
+        return Ok((Cow::Borrowed(source_dir), Cow::Borrowed(source_dir)));
+    };
+    if source_dir.starts_with(root) {
+        return Ok((Cow::Borrowed(source_dir), Cow::Borrowed(root)));
+    }
+
+    let outside = || ConversionError::SourceOutsideRoot { path: source_dir.to_path_buf(), root: root.to_path_buf() };
+    match (source_dir.canonicalize(), root.canonicalize()) {
+        (Ok(dir), Ok(root)) if dir.starts_with(&root) => Ok((Cow::Owned(dir), Cow::Owned(root))),
+        _ => Err(outside()),
+    }
+}
+
+
+/// Returns true if the path is a Docusaurus source document that should be converted.
+///
+/// Plain `.md` files are always accepted; `.mdx` files only when `include_mdx` is set.
+///
+/// # Arguments
+/// - `path`: Path of the candidate file
+/// - `include_mdx`: Whether `.mdx` files should be treated as sources
+pub fn is_markdown_file(path: &Path, include_mdx: bool) -> bool {
+    match path.extension().and_then(|s| s.to_str()) {
+        Some("md") => true,
+        Some("mdx") => include_mdx,
+        _ => false,
+    }
 }
 
 
 /// Converts Docusaurus markdown content to Quarto format.
 ///
-/// Performs two main transformations:
-/// 1. Frontmatter: Converts Docusaurus YAML frontmatter to Quarto format
+/// Performs three main transformations:
+/// 1. Frontmatter: Converts Docusaurus YAML (or `+++` TOML) frontmatter to Quarto YAML
 /// 2. Admonitions: Converts Docusaurus-style admonitions (:::note) to Quarto callout blocks
+/// 3. Links: Rewrites relative `.md`/`.mdx` link targets to `.qmd`
+///
+/// MDX `import`/`export` statements are removed and `<Tabs>` components are
+/// converted to Quarto tabsets before the line-by-line passes run. Lines inside
+/// fenced code blocks (```` ``` ```` or `~~~`) are never converted, though a
+/// Docusaurus `title="..."` or `{1,3-5}` on the opening fence becomes Quarto
+/// `filename=`/`code-line-numbers=` attributes.
 ///
 /// The function uses a state machine to track whether it's currently processing
-/// frontmatter (between --- markers) or regular content.
+/// frontmatter (between --- markers) or regular content. Frontmatter is only
+/// recognized when the opening marker is the first non-empty line of the file;
+/// any later `---` is kept as a Markdown thematic break. A stack of open callout
+/// fences gives nested admonitions progressively wider fences (`::::`,
+/// `:::::`) so each closing fence lines up with its opener. Pandoc divs that
+/// are already Quarto markup, such as the tabsets from `convert_tabs`, are
+/// copied along with their closing fences.
+///
+/// The output ends with a newline exactly when the input does, so diffing a
+/// source file against its conversion shows no spurious final-line change.
+///
+/// Docusaurus nests admonitions by giving the outer block more colons
+/// (`::::note` around `:::tip`). The stack remembers the source colon count of
+/// each opener so a close with a different count, a close with nothing open, or
+/// a block left open at the end of the file is logged at debug level and
+/// returned as a `Warning` by `convert_content_with_report`.
 ///
 /// # Arguments
 /// - `content`: The complete content of the markdown file as a string
@@ -110,88 +1036,468 @@ pub fn process_files(source_file: &Path, source_root: &Path, dest_root: &Path) -
 /// # Returns
 /// A new String containing the converted content in Quarto format
 pub fn convert_content(content: &str) -> String {
+    convert_content_with_options(content, &ConvertOptions::default())
+}
+
+/// Converts Docusaurus markdown content to Quarto format using the given callout title style.
+///
+/// Identical to `convert_content`, except admonition titles are emitted according
+/// to `title_style` instead of always as headings.
+///
+/// # Arguments
+/// - `content`: The complete content of the markdown file as a string
+/// - `title_style`: Whether callout titles become headings or `title=` attributes
+///
+/// # Returns
+/// A new String containing the converted content in Quarto format
+pub fn convert_content_with_title_style(content: &str, title_style: CalloutTitleStyle) -> String {
+    let opts = ConvertOptions {
+        callout_title_style: title_style,
+        ..ConvertOptions::default()
+    };
+    convert_content_with_options(content, &opts)
+}
+
+/// Converts Docusaurus markdown content to Quarto format as configured by `opts`.
+///
+/// Identical to `convert_content`, except link rewriting and tab conversion can
+/// be switched off and admonition titles follow `opts.callout_title_style`.
+/// When `opts.copy_images` is set, image references are rewritten to where the
+/// image folders land next to the converted file.
+///
+/// # Arguments
+/// - `content`: The complete content of the markdown file as a string
+/// - `opts`: Conversion options
+///
+/// # Returns
+/// A new String containing the converted content in Quarto format
+pub fn convert_content_with_options(content: &str, opts: &ConvertOptions) -> String {
+    convert_content_with_report(content, opts).0
+}
+
+/// Converts content like `convert_content_with_options`, also returning the warnings found.
+///
+/// Warnings cover unknown or malformed admonitions, unclosed frontmatter,
+/// rewritten link targets and JSX components left in the output. They are
+/// logged at debug level as they are found as well.
+///
+/// # Arguments
+/// - `content`: The complete content of the markdown file as a string
+/// - `opts`: Conversion options
+///
+/// # Returns
+/// The converted content and the warnings, in the order they were found
+pub fn convert_content_with_report(content: &str, opts: &ConvertOptions) -> (String, Vec<Warning>) {
+    let ConvertedContent { content, mut warnings, residual_components, .. } = convert_content_inner(content, opts, "", None);
+    warnings.extend(residual_components.iter().map(residual_component_warning));
+    (content, warnings)
+}
+
+fn residual_component_warning(component: &ResidualComponent) -> Warning {
+    Warning::new(
+        Some(component.line),
+        WarningKind::ResidualComponent,
+        format!("unconverted MDX component <{}>", component.name),
+    )
+}
+
+/// Result of converting content, before the residual component scan.
+struct ConvertedContent {
+    content: String,
+    /// YAML frontmatter that was emitted (if any), so callers can validate it
+    frontmatter: Option<String>,
+    warnings: Vec<Warning>,
+    /// JSX components left unconverted, numbered by their source line
+    residual_components: Vec<ResidualComponent>,
+}
+
+/// Converts content, also returning the emitted frontmatter and the warnings found.
+///
+/// `site_root` is the relative path from the document up to the site root (`""`
+/// for content converted on its own). `source_dir` is the folder of the source
+/// file, if any, where links to a README are checked for its `index` rename and
+/// relocated images for collisions with the document's own images.
+fn convert_content_inner(content: &str, opts: &ConvertOptions, site_root: &str, source_dir: Option<&Path>) -> ConvertedContent {
+    let title_style = opts.callout_title_style;
+    // A byte-order mark from Windows editors would hide a frontmatter opener on the first line
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    // Every pass emits `\n`, remember whether to switch back to the source's `\r\n`
+    let crlf = opts.preserve_line_endings && uses_crlf(content);
+    // Every line is re-emitted with a newline, remember whether the source ended with one
+    let missing_final_newline = !content.is_empty() && !content.ends_with('\n');
+
+    // Every pass below may add or remove lines, the map leads warnings back to the source line
+    let mut line_map = LineMap::default();
+
+    // Custom transforms see the source before any built-in pass
+    let content = if opts.line_transforms.is_empty() {
+        Cow::Borrowed(content)
+    } else {
+        let (content, map) = opts.line_transforms.apply_mapped(content);
+        line_map = map;
+        Cow::Owned(content)
+    };
+
+    // MDX import/export statements are invalid in Quarto, drop them up front
+    let (content, removed_statements, map) = mdx::strip_mdx_statements_mapped(&content);
+    line_map = map.after(&line_map);
+    if !removed_statements.is_empty() {
+        debug!("  🧹 Removed {} MDX import/export statement(s)", removed_statements.len());
+    }
+
+    // JSX comments are MDX-only too and would be rendered as text
+    let (content, map, unclosed_comment) = mdx::strip_jsx_comments_mapped(&content);
+    let unclosed_comment = unclosed_comment.map(|line| line_map.input_line(line) + 1);
+    line_map = map.after(&line_map);
+
+    // Docusaurus <Tabs> components become Quarto panel tabsets
+    let content = if opts.convert_tabs {
+        let (content, map) = tabs::convert_tabs_mapped(&content);
+        line_map = map.after(&line_map);
+        content
+    } else {
+        content
+    };
+
+    // Raw <details> HTML becomes `:::details` blocks, converted with the admonitions below
+    let content = if opts.convert_details {
+        let (content, map) = details::convert_details_html_mapped(&content);
+        line_map = map.after(&line_map);
+        content
+    } else {
+        content
+    };
+
+    // <Admonition> components become `:::` blocks and are converted with the rest below
+    let (content, map) = admonition_components::convert_admonition_components_mapped(&content);
+    line_map = map.after(&line_map);
+
+    // Registered components such as <Highlight> become Quarto spans and divs
+    let (content, map) = components::convert_components_mapped(&content, &opts.components);
+    line_map = map.after(&line_map);
+
+    // Components no pass converted stay in the output, report them at their source line
+    let residual_components: Vec<ResidualComponent> = find_jsx_components(&content)
+        .into_iter()
+        .map(|component| ResidualComponent { line: line_map.input_line(component.line - 1) + 1, ..component })
+        .collect();
+
+    // A blog summary becomes the description, unless the post sets its own
+    let mut metadata = Cow::Borrowed(&opts.metadata);
+    if opts.truncate_marker == TruncateMarker::Description
+        && let Some(summary) = truncate_summary(&content)
+    {
+        let mut with_description = serde_yaml::Mapping::new();
+        with_description.insert("description".into(), summary.into());
+        for (key, value) in opts.metadata.iter() {
+            if !with_description.contains_key(key) {
+                with_description.insert(key.clone(), value.clone());
+            }
+        }
+        metadata = Cow::Owned(with_description);
+    }
 
     let mut result = String::new();
-    let mut in_frontmatter = false;
+    // Delimiter of the frontmatter block currently being collected ("---" or "+++")
+    let mut frontmatter_delimiter: Option<&str> = None;
     let mut frontmatter_lines = Vec::new();
+    // Frontmatter may only open on the first non-empty line, later "---" are thematic breaks
+    let mut frontmatter_allowed = true;
+    let mut has_frontmatter = false;
+    let mut emitted_frontmatter = None;
+    // Every open admonition, its body is collected in `result` until the block closes
+    let mut fence_stack: Vec<OpenAdmonition> = Vec::new();
+    // Number of open admonitions when each open Pandoc div (`::: {.panel-tabset}`) started,
+    // a closing fence at that depth closes the div rather than an admonition
+    let mut div_stack: Vec<usize> = Vec::new();
+    let mut code_fences = fence::FenceTracker::default();
+    let mut warnings = Vec::new();
+    if let Some(line) = unclosed_comment {
+        push_warning(&mut warnings, Warning::new(
+            Some(line),
+            WarningKind::UnclosedComment,
+            "JSX comment `{/*` is never closed, keeping the rest of the file".to_string(),
+        ));
+    }
+    let mut in_html_comment = false;
 
-    
     // Process the file line by line
-    for line in content.lines() {
-        // Handle frontmatter (All YAML between these "---" markers)
-        if line == "---" {
-            if !in_frontmatter {
-                in_frontmatter = true;
-                continue;
-            } else {
-                // End of frontmatter - convert and add to result
+    for (index, line) in content.lines().enumerate() {
+        let line_number = line_map.input_line(index) + 1;
+
+        // Handle frontmatter (YAML between "---" or TOML between "+++" markers)
+        if let Some(delimiter) = frontmatter_delimiter {
+            if frontmatter::frontmatter_delimiter(line) == Some(delimiter) {
+                // End of frontmatter - convert and add to result as YAML
+                result.push_str("---\n");
+                let dropped_keys: Vec<&str> = opts.dropped_frontmatter_keys.iter().map(String::as_str).collect();
+                let frontmatter_opts = FrontmatterOptions { metadata: &metadata, doc_id: opts.doc_id, site_root, dropped_keys: &dropped_keys, authors: &opts.authors };
+                let frontmatter = if delimiter == "+++" {
+                    convert_toml_frontmatter_with_options(&frontmatter_lines, &frontmatter_opts)
+                } else {
+                    convert_frontmatter_with_options(&frontmatter_lines, &frontmatter_opts)
+                };
+                result.push_str(&frontmatter);
                 result.push_str("---\n");
-                result.push_str(&convert_frontmatter(&frontmatter_lines));
-                // result.push_str("---\n");
+                emitted_frontmatter = Some(frontmatter);
                 frontmatter_lines.clear();
-                continue;
+                frontmatter_delimiter = None;
+                frontmatter_allowed = false;
+            } else {
+                // Collect frontmatter lines for processing
+                frontmatter_lines.push(line);
             }
+            continue;
         }
 
-        if in_frontmatter {
-            // Collect frontmatter lines for processing
-            frontmatter_lines.push(line);
-        } else {
-            // Convert admonitions in the content
-            let converted_line = convert_admonitions(line);
-            result.push_str(&converted_line);
+        if frontmatter_allowed && let Some(delimiter) = frontmatter::frontmatter_delimiter(line) {
+            frontmatter_delimiter = Some(delimiter);
+            has_frontmatter = true;
+            continue;
+        }
+        if !line.trim().is_empty() {
+            frontmatter_allowed = false;
+        }
+
+        // Code blocks are copied verbatim so documented `:::` syntax is not converted
+        let was_in_code = code_fences.is_open();
+        if code_fences.update(line) {
+            // Only the opening fence carries Docusaurus metadata worth converting
+            if !was_in_code {
+                result.push_str(&convert_code_fence(line));
+            } else {
+                result.push_str(line);
+            }
             result.push('\n');
+            continue;
         }
-    }
 
-    result
-}
+        // The rest of a multi-line HTML comment is copied verbatim, commented-out markup stays as written
+        if in_html_comment {
+            in_html_comment = !line.contains("-->");
+            result.push_str(line);
+            result.push('\n');
+            continue;
+        }
+        in_html_comment = comments::opens_multiline_comment(line);
 
-/// Converts Docusaurus frontmatter fields to Quarto equivalents.
-///
-/// Currently handles the following conversions:
-/// - `sidebar_position` → `order`
-/// - All other fields are preserved as-is
-///
-/// # Arguments
-/// - `lines`: Slice of string slices representing frontmatter lines (without --- delimiters)
-///
-/// # Returns
-/// A String containing the converted frontmatter (without --- delimiters)
-///
-/// # Note
-/// Future enhancements could include additional field mappings such as:
-/// - `sidebar_label` → `title` (if title is not present)
-/// - Custom metadata transformations
-pub fn convert_frontmatter(lines: &[&str]) -> String {
-    
-    let mut result = String::new();
+        // Quarto listings use the description instead of the truncate marker
+        if opts.truncate_marker != TruncateMarker::Keep && is_truncate_marker(line) {
+            continue;
+        }
+
+        // Tooling directives such as <!-- prettier-ignore --> mean nothing to Quarto
+        if is_comment_directive(line) {
+            continue;
+        }
 
-    for line in lines {
-        // Convert 'sidebar_position' to 'order'
-        if line.trim().starts_with("sidebar_position") {
-            let value = line.split(':').nth(1).unwrap_or("").trim();
-            result.push_str(&format!("order: {}\n", value));
+        // Convert admonitions in the content, widening the fence per nesting level
+        let converted_line = if pandoc_div_start().is_match(line) {
+            // Already Quarto markup, e.g. a tabset written by `convert_tabs`
+            div_stack.push(fence_stack.len());
+            line.to_string()
+        } else if admonition_end().is_match(line) && div_stack.last() == Some(&fence_stack.len()) {
+            div_stack.pop();
+            line.to_string()
+        } else if let Some(caps) = admonition_start().captures(line) {
+            let width = CALLOUT_FENCE_WIDTH + fence_stack.len();
+            // A single-line admonition closes itself and leaves nothing open
+            let inline = split_inline_admonition(&caps["rest"]).is_some();
+            if callout_type(&caps["type"], &opts.admonition_types).is_none() {
+                push_warning(&mut warnings, Warning::new(
+                    Some(line_number),
+                    WarningKind::UnknownAdmonition,
+                    format!("unknown admonition type `{}` has no callout mapping, passing it through", &caps["type"]),
+                ));
+            }
+            if !caps["indent"].is_empty() {
+                push_warning(&mut warnings, Warning::new(
+                    Some(line_number),
+                    WarningKind::IndentedAdmonition,
+                    "indented admonition kept indented, Quarto only renders it as a callout inside a list item".to_string(),
+                ));
+            }
+            if !inline {
+                // The block is converted as a whole once its closing fence is seen
+                let opener = if opts.copy_images { images::rewrite_image_references_in(line, &opts.image_folders, source_dir) } else { line.to_string() };
+                fence_stack.push(OpenAdmonition { colons: caps["colons"].len(), width, opener, start: result.len() });
+                continue;
+            }
+            // The inline body is regular text that may hold links
+            let converted = convert_admonition_fence_with_types(line, width, title_style, &opts.admonition_types);
+            let converted = resolve_aliases(&converted, line_number, opts, &mut warnings);
+            if opts.convert_links { rewrite_doc_links(converted, line_number, opts, source_dir, &mut warnings) } else { converted }
+        } else if let Some(caps) = admonition_end().captures(line) {
+            match fence_stack.pop() {
+                Some(open) => {
+                    if open.colons != caps["colons"].len() {
+                        push_warning(&mut warnings, Warning::new(
+                            Some(line_number),
+                            WarningKind::MismatchedFence,
+                            format!("closing fence `{}` does not match its opening `{}`", line, ":".repeat(open.colons)),
+                        ));
+                    }
+                    // A div left open inside the admonition cannot outlive it
+                    div_stack.retain(|&depth| depth <= fence_stack.len());
+                    let body = result.split_off(open.start);
+                    let block = format!("{}\n{}{}", open.opener, body, line);
+                    result.push_str(&convert_admonitions_block(&block, open.width, title_style, &opts.admonition_types));
+                    result.push('\n');
+                    continue;
+                }
+                None => {
+                    push_warning(&mut warnings, Warning::new(
+                        Some(line_number),
+                        WarningKind::UnmatchedFence,
+                        format!("closing fence `{}` has no open admonition", line),
+                    ));
+                    line.to_string()
+                }
+            }
         } else {
+            let line = resolve_aliases(line, line_number, opts, &mut warnings);
+            let line = convert_heading_attributes(&line);
+            let line = convert_math_delimiters(&line);
+            let line = if opts.normalize_footnotes { convert_footnotes(&line) } else { line };
+            if opts.convert_links { rewrite_doc_links(line, line_number, opts, source_dir, &mut warnings) } else { line }
+        };
+        // Image folders are copied next to the output, point references at the copies
+        let converted_line = if opts.copy_images {
+            images::rewrite_image_references_in(&converted_line, &opts.image_folders, source_dir)
+        } else {
+            converted_line
+        };
+        result.push_str(&converted_line);
+        result.push('\n');
+    }
+
+    if !fence_stack.is_empty() {
+        push_warning(&mut warnings, Warning::new(
+            None,
+            WarningKind::UnclosedAdmonition,
+            format!("{} admonition(s) left open at end of file", fence_stack.len()),
+        ));
+    }
+    // Unclosed blocks still get their callout opening, innermost first so outer bodies hold them
+    while let Some(open) = fence_stack.pop() {
+        let body = result.split_off(open.start);
+        let block = format!("{}\n{}", open.opener, body);
+        result.push_str(&convert_admonitions_block(&block, open.width, title_style, &opts.admonition_types));
+        result.push('\n');
+    }
+
+    // An unterminated frontmatter block is kept as written rather than silently dropped
+    if let Some(delimiter) = frontmatter_delimiter {
+        push_warning(&mut warnings, Warning::new(
+            None,
+            WarningKind::UnclosedFrontmatter,
+            format!("Frontmatter opened with `{}` is never closed, leaving it unchanged", delimiter),
+        ));
+        result.push_str(delimiter);
+        result.push('\n');
+        for line in &frontmatter_lines {
             result.push_str(line);
             result.push('\n');
         }
     }
-    result
- }
 
+    // Documents without frontmatter still get the bulk metadata
+    if !has_frontmatter && !metadata.is_empty() {
+        let metadata = convert_frontmatter_with_metadata(&[], &metadata);
+        result = format!("---\n{}---\n{}", metadata, result);
+        emitted_frontmatter = Some(metadata);
+    }
+
+    if missing_final_newline && result.ends_with('\n') {
+        result.pop();
+    }
+
+    if crlf {
+        result = result.replace("\r\n", "\n").replace('\n', "\r\n");
+    }
+
+    ConvertedContent { content: result, frontmatter: emitted_frontmatter, warnings, residual_components }
+}
+
+/// Returns true if more lines of `content` end with `\r\n` than with a bare `\n`.
+fn uses_crlf(content: &str) -> bool {
+    let crlf = content.matches("\r\n").count();
+    crlf > content.matches('\n').count() - crlf
+}
+
+/// An admonition opened in `convert_content_inner` whose closing fence has not been seen yet.
+struct OpenAdmonition {
+    /// Number of colons of the opening fence, which the closing fence should repeat
+    colons: usize,
+    /// Width of the emitted Quarto fence
+    width: usize,
+    /// The opening `:::type Title` line
+    opener: String,
+    /// Offset in the output where the block's converted body starts
+    start: usize,
+}
+
+/// Logs a warning and keeps it for the caller.
+///
+/// Warnings are only logged at debug level, callers summarize them by kind
+/// (see `count_warnings`) and show the detail on request.
+fn push_warning(warnings: &mut Vec<Warning>, warning: Warning) {
+    debug!("  ⚠ {}", warning);
+    warnings.push(warning);
+}
+
+/// Resolves the `@site/` aliases on a line, warning about the ones left unchanged.
+fn resolve_aliases(line: &str, line_number: usize, opts: &ConvertOptions, warnings: &mut Vec<Warning>) -> String {
+    let (resolved, unresolved) = links::resolve_site_aliases_reporting(line, opts.site_base.as_deref());
+    for alias in unresolved {
+        push_warning(warnings, Warning::new(
+            Some(line_number),
+            WarningKind::UnresolvedAlias,
+            format!("cannot resolve path alias `{}`, leaving it unchanged", alias),
+        ));
+    }
+    resolved
+}
+
+/// Rewrites doc link targets on a line, noting when any target changed.
+///
+/// With a `source_dir` and `opts.readme_as_index`, links to a README point at the `index` page it is written as.
+fn rewrite_doc_links(line: String, line_number: usize, opts: &ConvertOptions, source_dir: Option<&Path>, warnings: &mut Vec<Warning>) -> String {
+    let extension = &opts.output_extension;
+    let rewritten = match source_dir.filter(|_| opts.readme_as_index) {
+        Some(source_dir) => links::convert_links_to_landing_pages(&line, extension, source_dir),
+        None => convert_links_with_extension(&line, extension),
+    };
+    if rewritten != line {
+        debug!("  🔗 Line {}: rewrote link target(s) to .{}", line_number, extension);
+        warnings.push(Warning::new(
+            Some(line_number),
+            WarningKind::RewrittenLink,
+            format!("rewrote .md/.mdx link target(s) to .{}", extension),
+        ));
+    }
+    rewritten
+}
 
 /// Converts a single line from Docusaurus admonition syntax to Quarto callout syntax.
 ///
-/// Docusaurus uses `:::type Title` syntax, while Quarto uses `:::: {.callout-type}` syntax.
+/// Docusaurus uses `:::type Title` (or `:::type[Title]`) syntax, while Quarto uses
+/// `:::: {.callout-type}` syntax. A space between the colons and the type
+/// (`::: note`) is tolerated. The single-line form `:::note Some text :::`
+/// becomes a complete callout with the text as its body. This is the stateless, single-level form;
+/// `convert_content` tracks nesting and calls `convert_admonition_fence` with the
+/// appropriate fence width.
 ///
 /// # Supported Admonition Types
+/// See `DEFAULT_CALLOUT_TYPES`:
 /// - note → note
 /// - tip → tip
 /// - info → note
-/// - caution → caution
+/// - caution → warning
 /// - warning → warning
 /// - danger → important
+/// - details → note with `collapse="true"`, the summary becoming its title;
+///   `:::details[Summary]{open}` is expanded with `collapse="false"`
 ///
 /// # Arguments
 /// - `line`: A single line from the markdown file
@@ -200,106 +1506,429 @@ pub fn convert_frontmatter(lines: &[&str]) -> String {
 /// - Converted callout syntax if the line matches an admonition pattern
 /// - Original line unchanged if no pattern matches
 ///
-
 pub fn convert_admonitions(line: &str) -> String {
-    
+    convert_admonition_fence(line, CALLOUT_FENCE_WIDTH, CalloutTitleStyle::default())
+} //end of function
 
-    let admonition_start = Regex::new(r"^:::(\w)+(.*)$").unwrap();
-    let admonition_end = Regex::new(r"^:::$").unwrap();
+
+/// Converts a single admonition line using a Quarto fence of the given width.
+///
+/// Opening lines become `{.callout-type}` fences and closing lines become a bare
+/// fence, both using `width` colons so nested callouts can be closed unambiguously.
+/// Leading indentation (e.g. inside a list item) is preserved.
+///
+/// # Arguments
+/// - `line`: A single line from the markdown file
+/// - `width`: Number of colons to use for the emitted Quarto fence
+/// - `title_style`: Whether a title becomes a `## Title` heading or a `title="..."` attribute
+///
+/// # Returns
+/// - Converted callout syntax if the line matches an admonition pattern
+/// - Original line unchanged if no pattern matches
+pub fn convert_admonition_fence(line: &str, width: usize, title_style: CalloutTitleStyle) -> String {
+    convert_admonition_fence_with_types(line, width, title_style, &BTreeMap::new())
+}
+
+/// Converts a single admonition line, consulting `custom_types` before the built-in mapping.
+///
+/// `custom_types` maps lowercase Docusaurus admonition names (e.g. `hint`) to
+/// Quarto callout types (e.g. `tip`), typically loaded from a `Config` file.
+/// Types with no mapping are passed through unchanged as `.callout-<type>`.
+///
+/// # Arguments
+/// - `line`: A single line from the markdown file
+/// - `width`: Number of colons to use for the emitted Quarto fence
+/// - `title_style`: Whether a title becomes a `## Title` heading or a `title="..."` attribute
+/// - `custom_types`: Additional admonition → callout type mappings
+///
+/// # Returns
+/// - Converted callout syntax if the line matches an admonition pattern
+/// - Original line unchanged if no pattern matches
+pub fn convert_admonition_fence_with_types(line: &str, width: usize, title_style: CalloutTitleStyle, custom_types: &BTreeMap<String, String>) -> String {
+    let fence = ":".repeat(width);
 
     // Convert opening admonitin syntax
-    if let Some(caps) = admonition_start.captures(line) {
-        let admonition_type = &caps[1];
-        let title = caps.get(2).map(|m| m.as_str().trim()).unwrap_or("");
+    if let Some(caps) = admonition_start().captures(line) {
+        // Indentation (e.g. inside a list item) is carried over to every emitted line
+        let indent = &caps["indent"];
+        let fence = format!("{}{}", indent, fence);
+        let admonition_type = &caps["type"];
+        let rest = &caps["rest"];
+
+        // Single-line form `:::note Some text :::` carries its body and closing fence
+        let (rest, body) = match split_inline_admonition(rest) {
+            Some((label, body)) => (label, Some(body)),
+            None => (rest, None),
+        };
+
+        // remark-directive attributes may follow the label: `:::details[Summary]{open}`
+        let (rest, attributes) = match rest.trim_end().strip_suffix('}').and_then(|r| r.rsplit_once('{')) {
+            Some((label, attributes)) if label.trim().is_empty() || label.trim_end().ends_with(']') => (label, attributes),
+            _ => (rest, ""),
+        };
+
+        // Docusaurus also accepts an inline label directly after the type: `:::info[Label]`
+        let title = match rest.strip_prefix('[').and_then(|r| r.trim_end().strip_suffix(']')) {
+            Some(label) => label.trim(),
+            None => rest.trim(),
+        };
 
         // Map Docusaurus admonitions to Quarto callout types
-        let quarto_type = match admonition_type.to_lowercase().as_str() {
-            "note" => "note",
-            "tip" => "tip",
-            "info" => "note",
-            "caution" => "caution",
-            "warning" => "warning",
-            "danger" => "important",
-            _ => admonition_type,
+        let quarto_type = callout_type(admonition_type, custom_types).unwrap_or(admonition_type);
+
+        // `:::details` becomes a collapsed callout, whose summary has to stay visible as the title;
+        // `{open}` keeps it collapsible but expanded
+        let collapse = is_details(admonition_type, custom_types);
+        let class = if collapse {
+            let expanded = attributes.split_whitespace().any(|attribute| attribute == "open");
+            format!(".callout-{} collapse=\"{}\"", quarto_type, !expanded)
+        } else {
+            format!(".callout-{}", quarto_type)
         };
 
         // Build Quarto callout syntax
-        if title.is_empty() {
-            format!(":::: {{{}}}", quarto_type)
+        let opening = if title.is_empty() {
+            format!("{} {{{}}}", fence, class)
+        } else if title_style == CalloutTitleStyle::Heading && !collapse {
+            format!("{} {{{}}}\n{}## {}", fence, class, indent, title)
         } else {
-            format!(":::: {{.callout-{}}}\n## {}", quarto_type, title)
+            format!("{} {{{} title=\"{}\"}}", fence, class, escape_attribute(title))
+        };
+
+        match body {
+            Some("") => format!("{}\n{}", opening, fence),
+            Some(body) => format!("{}\n{}{}\n{}", opening, indent, body, fence),
+            None => opening,
         }
      }
 
     // Conver closing admonition syntax
-    else if admonition_end.is_match(line) {
-        "::::".to_string()
+    else if let Some(caps) = admonition_end().captures(line) {
+        format!("{}{}", &caps["indent"], fence)
     }
     // Return line unchanged if it is not admonition
     else {
         line.to_string()
     }
-} //end of function
+}
+
+/// Splits the remainder of a single-line admonition (`:::note Some text :::`).
+///
+/// Returns `None` unless the line ends with a closing fence of three or more
+/// colons. Otherwise returns the `[Label]` written before the text (or `""`)
+/// and the text itself.
+fn split_inline_admonition(rest: &str) -> Option<(&str, &str)> {
+    let trimmed = rest.trim_end();
+    let inner = trimmed.trim_end_matches(':');
+    if trimmed.len() - inner.len() < 3 {
+        return None;
+    }
+
+    let inner = inner.trim();
+    if inner.starts_with('[')
+        && let Some(end) = inner.find(']')
+    {
+        return Some((&inner[..=end], inner[end + 1..].trim()));
+    }
+    Some(("", inner))
+}
+
+/// Maps a Docusaurus admonition type to its Quarto callout type.
+///
+/// Custom mappings take precedence over `DEFAULT_CALLOUT_TYPES`. Returns `None`
+/// for types that neither knows about.
+fn callout_type<'a>(admonition_type: &str, custom_types: &'a BTreeMap<String, String>) -> Option<&'a str> {
+    let name = admonition_type.to_lowercase();
+    if let Some(mapped) = custom_types.get(&name) {
+        return Some(mapped.as_str());
+    }
+
+    DEFAULT_CALLOUT_TYPES
+        .iter()
+        .find(|(admonition, _)| *admonition == name)
+        .map(|(_, callout)| *callout)
+}
+
+/// Converts a complete admonition block, from its `:::type` opening line to the matching `:::`.
+///
+/// The opening line becomes the callout opening, with the title placed per
+/// `title_style`, and the closing line a fence of `width` colons. Everything
+/// in between is the body, copied as given: `convert_content` passes it already
+/// converted, nested callouts included. Blank lines at the start and end of the
+/// body are trimmed, Quarto does not need them around callout content. A block
+/// without a closing fence keeps its body untouched and gets no closing fence.
+///
+/// # Arguments
+/// - `block`: The opening line, the body lines and the closing line
+/// - `width`: Number of colons to use for the emitted Quarto fences
+/// - `title_style`: Whether a title becomes a `## Title` heading or a `title="..."` attribute
+/// - `custom_types`: Additional admonition → callout type mappings
+///
+/// # Returns
+/// The Quarto callout without a trailing newline, or `block` unchanged if its
+/// first line does not open an admonition
+pub fn convert_admonitions_block(block: &str, width: usize, title_style: CalloutTitleStyle, custom_types: &BTreeMap<String, String>) -> String {
+    let mut lines: Vec<&str> = block.lines().collect();
+    if lines.is_empty() || !admonition_start().is_match(lines[0]) {
+        return block.to_string();
+    }
+    let opener = lines.remove(0);
+    let closing = if lines.last().is_some_and(|line| admonition_end().is_match(line)) { lines.pop() } else { None };
+
+    let mut body = lines.as_slice();
+    if closing.is_some() {
+        while let [first, rest @ ..] = body
+            && first.trim().is_empty()
+        {
+            body = rest;
+        }
+        while let [rest @ .., last] = body
+            && last.trim().is_empty()
+        {
+            body = rest;
+        }
+    }
+
+    let mut converted = convert_admonition_fence_with_types(opener, width, title_style, custom_types);
+    for line in body {
+        converted.push('\n');
+        converted.push_str(line);
+    }
+    if let Some(closing) = closing {
+        converted.push('\n');
+        converted.push_str(&convert_admonition_fence(closing, width, title_style));
+    }
+    converted
+}
+
+/// Returns true for the built-in `:::details` collapsible, unless a custom mapping overrides it.
+fn is_details(admonition_type: &str, custom_types: &BTreeMap<String, String>) -> bool {
+    let name = admonition_type.to_lowercase();
+    name == "details" && !custom_types.contains_key(&name)
+}
+
+/// Returns the compiled pattern matching an admonition opening line.
+fn admonition_start() -> &'static Regex {
+    ADMONITION_START.get_or_init(|| Regex::new(r"^(?P<indent>[ \t]*)(?P<colons>:{3,})[ \t]*(?P<type>\w+)(?P<rest>.*)$").unwrap())
+}
+
+/// Returns the compiled pattern matching the opening fence of a Pandoc div with attributes.
+fn pandoc_div_start() -> &'static Regex {
+    PANDOC_DIV_START.get_or_init(|| Regex::new(r"^[ \t]*:{3,}[ \t]*\{").unwrap())
+}
+
+/// Returns the compiled pattern matching an admonition closing line.
+fn admonition_end() -> &'static Regex {
+    ADMONITION_END.get_or_init(|| Regex::new(r"^(?P<indent>[ \t]*)(?P<colons>:{3,})$").unwrap())
+}
 
 
 /// Copies the img folder from source directory to destination directory.
 ///
 /// Docusaurus projects often have img folders alongside markdown files containing
 /// referenced images. This function preserves that structure in the output.
+/// Equivalent to `copy_image_folders` with only the `img` folder name.
 ///
 /// # Arguments
 /// - `source_file`: Path to the source markdown file
 /// - `dest_file`: Path to the destination markdown file
 ///
 /// # Returns
-/// - `Ok(())` if img folder doesn't exist or is successfully copied
+/// - `Ok(true)` if an img folder was found and copied
+/// - `Ok(false)` if there is no img folder next to the source file
 /// - `Err` if directory creation or file copying fails
 ///
 /// # Behavior
 /// - If no img folder exists in the source directory, the function succeeds silently
-/// - If img folder exists, creates it in destination and copies all files
-/// - Preserves original filenames
-///
-pub fn copy_img_folder(source_file: &Path, dest_file: &Path) -> Result<(), std::io::Error> {
-    
-    // Get the parent directory of the source file
-    if let Some(source_parent) = source_file.parent() {
-        let img_folder = source_parent.join("img");
-        
-        // Check if img folder exists
-        if img_folder.exists() && img_folder.is_dir() {
-            // Get destination parent directory
-            if let Some(dest_parent) = dest_file.parent() {
-                let dest_img = dest_parent.join("img");
-                
-                // Create destination img folder
-                fs::create_dir_all(&dest_img)?;
-                
-                // Copy all files from source img to dest img
-                for entry in fs::read_dir(&img_folder)? {
-                    let entry = entry?;
-                    let file_name = entry.file_name();
-                    let dest_file_path = dest_img.join(&file_name);
-                    fs::copy(entry.path(), dest_file_path)?;
-                }
-            }
+/// - If img folder exists, creates it in destination and copies all files recursively
+/// - Preserves original filenames and nested folder structure
+/// - Copied files get the default `ImageMode` permissions
+///
+pub fn copy_img_folder(source_file: &Path, dest_file: &Path) -> Result<bool, std::io::Error> {
+    copy_image_folders(source_file, dest_file, &["img"])
+}
+
+/// Copies every image folder with one of the given names from the source directory
+/// to the destination directory.
+///
+/// Docusaurus sites keep images in `img`, `images`, `assets` or `_assets` folders
+/// next to the docs; each folder that exists is copied under the same name,
+/// including nested subfolders such as `img/diagrams/foo.png`.
+///
+/// # Arguments
+/// - `source_file`: Path to the source markdown file
+/// - `dest_file`: Path to the destination markdown file
+/// - `folder_names`: Names of the image folders to look for
+///
+/// # Returns
+/// - `Ok(true)` if at least one image folder was found and copied
+/// - `Ok(false)` if none of the folders exist next to the source file
+/// - `Err` if directory creation or file copying fails
+pub fn copy_image_folders<S: AsRef<str>>(source_file: &Path, dest_file: &Path, folder_names: &[S]) -> Result<bool, std::io::Error> {
+    copy_image_folders_with_mode(source_file, dest_file, folder_names, ImageMode::default())
+}
+
+/// Copies image folders like `copy_image_folders`, giving the copies `mode` permissions.
+///
+/// Normalizing the permissions keeps odd source modes (an executable bit from a
+/// generator script, a file only its owner can read) out of the published site.
+/// Copies that are already up to date are left alone.
+pub fn copy_image_folders_with_mode<S: AsRef<str>>(source_file: &Path, dest_file: &Path, folder_names: &[S], mode: ImageMode) -> Result<bool, std::io::Error> {
+    copy_image_folders_to(source_file, dest_file, folder_names, &mut ImageCopier::new(mode, true))
+}
+
+fn copy_image_folders_to<S: AsRef<str>>(source_file: &Path, dest_file: &Path, folder_names: &[S], copier: &mut ImageCopier) -> Result<bool, std::io::Error> {
+    let (Some(source_parent), Some(dest_parent)) = (source_file.parent(), dest_file.parent()) else {
+        return Ok(false);
+    };
+
+    let mut copied = false;
+    for name in folder_names {
+        let img_folder = source_parent.join(name.as_ref());
+
+        // Check if the image folder exists
+        if img_folder.is_dir() {
+            let dest_img = dest_parent.join(name.as_ref());
+
+            // Copy the folder, including any nested subfolders
+            copy_dir_recursive(&img_folder, &dest_img, copier)?;
+            copied = true;
         }
     }
 
-
-    Ok(())
-    
+    Ok(copied)
 }
 
+/// Copies images referenced from a parent directory's image folder next to the output.
+///
+/// `convert_content_with_options` rewrites `../img/foo.png` to `img/foo.png`, so the
+/// referenced file has to be placed there. Missing images are skipped with a warning.
+///
+/// # Returns
+/// - `Ok(true)` if at least one image was copied
+fn copy_relocated_images<S: AsRef<str>>(content: &str, source_file: &Path, dest_file: &Path, folder_names: &[S], copier: &mut ImageCopier) -> Result<bool, std::io::Error> {
+    let (Some(source_parent), Some(dest_parent)) = (source_file.parent(), dest_file.parent()) else {
+        return Ok(false);
+    };
 
+    let mut copied = false;
+    for (original, relocated) in images::relocated_images_in(content, folder_names, Some(source_parent)) {
+        let source_image = source_parent.join(&original);
+        let dest_image = dest_parent.join(&relocated);
 
+        if !source_image.is_file() {
+            warn!("  ⚠ Referenced image not found: {:?}", source_image);
+            continue;
+        }
+        if is_same_file(&source_image, &dest_image) {
+            continue;
+        }
+        if let Some(parent) = dest_image.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        copier.copy(&source_image, &dest_image)?;
+        copied = true;
+    }
 
+    Ok(copied)
+}
 
+/// Recursively copies the contents of `source_dir` into `dest_dir`.
+///
+/// Entries are visited in file name order, so copies (and their log lines) come
+/// out the same on every platform. Symlinked files are copied by content, while
+/// symlinked directories are skipped so a link cycle cannot make the copy run
+/// forever. Files whose destination is the source file itself (converting a tree
+/// into itself) are left alone.
+fn copy_dir_recursive(source_dir: &Path, dest_dir: &Path, copier: &mut ImageCopier) -> Result<(), std::io::Error> {
+    fs::create_dir_all(dest_dir)?;
 
+    for entry in WalkDir::new(source_dir).min_depth(1).sort_by_file_name() {
+        let entry = entry.map_err(std::io::Error::other)?;
+        let relative = entry
+            .path()
+            .strip_prefix(source_dir)
+            .map_err(std::io::Error::other)?;
+        let target = dest_dir.join(relative);
+        let file_type = entry.file_type();
 
+        if file_type.is_dir() {
+            fs::create_dir_all(&target)?;
+        } else if file_type.is_symlink() && entry.path().is_dir() {
+            warn!("  ⚠ Skipping symlinked directory {:?}", entry.path());
+        } else if is_same_file(entry.path(), &target) {
+            continue;
+        } else {
+            copier.copy(entry.path(), &target)?;
+        }
+    }
 
+    Ok(())
+}
 
+/// Copies image files one at a time, counting what was copied and what was already up to date.
+struct ImageCopier {
+    mode: ImageMode,
+    skip_unchanged: bool,
+    /// Destinations of the files copied so far
+    copied: Vec<PathBuf>,
+    skipped: usize,
+}
+
+impl ImageCopier {
+    fn new(mode: ImageMode, skip_unchanged: bool) -> ImageCopier {
+        ImageCopier { mode, skip_unchanged, copied: Vec::new(), skipped: 0 }
+    }
 
+    /// Copies one image file, applying the permission mode and the source's modification time.
+    ///
+    /// With `skip_unchanged`, a destination with the source's size and
+    /// modification time is taken to be an earlier copy and left alone. A
+    /// read-only destination (e.g. a copy of a read-only source) is replaced.
+    fn copy(&mut self, source: &Path, dest: &Path) -> Result<(), std::io::Error> {
+        let source_metadata = fs::metadata(source)?;
+        let modified = source_metadata.modified()?;
 
+        if self.skip_unchanged
+            && let Ok(dest_metadata) = fs::metadata(dest)
+            && dest_metadata.len() == source_metadata.len()
+            && dest_metadata.modified().is_ok_and(|dest_modified| dest_modified == modified)
+        {
+            self.skipped += 1;
+            return Ok(());
+        }
+
+        // A read-only file cannot be opened for writing, but its folder lets it be replaced
+        if fs::symlink_metadata(dest).is_ok_and(|metadata| metadata.permissions().readonly()) {
+            fs::remove_file(dest)?;
+        }
+        let mut file = fs::File::create(dest)?;
+        std::io::copy(&mut fs::File::open(source)?, &mut file)?;
+        // Matching times are what lets the next run recognize the copy, set
+        // them while the file is still open for writing
+        file.set_modified(modified)?;
+        drop(file);
 
+        // Permissions last, they may make the copy read-only
+        match self.mode {
+            ImageMode::Keep => fs::set_permissions(dest, source_metadata.permissions())?,
+            #[cfg(unix)]
+            ImageMode::Mode(bits) => {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(dest, fs::Permissions::from_mode(bits))?;
+            }
+            #[cfg(not(unix))]
+            ImageMode::Mode(_) => {}
+        }
+        self.copied.push(dest.to_path_buf());
+        Ok(())
+    }
+}
 
+/// Returns true if both paths exist and resolve to the same file.
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
@@ -0,0 +1,55 @@
+//! Maps the lines of rewritten content back to the source file.
+//!
+//! The passes that run before the line-by-line conversion (MDX statement
+//! removal, tabs, components, ...) add and remove lines. Each pass records
+//! which of its input lines every output line comes from, and the maps are
+//! chained so warnings found in the rewritten content name the source line.
+
+use std::iter;
+
+/// Input line of every line of a pass's output, both 0-based.
+///
+/// The default map is the identity, for a pass that did not run.
+#[derive(Debug, Default)]
+pub(crate) struct LineMap {
+    lines: Vec<usize>,
+    /// Length of the output whose lines are already recorded
+    tracked: usize,
+}
+
+impl LineMap {
+    /// Records that the output lines completed since the last call come from input line `input_line`.
+    pub(crate) fn track(&mut self, output: &str, input_line: usize) {
+        let start = self.tracked.min(output.len());
+        let completed = output[start..].matches('\n').count();
+        self.lines.extend(iter::repeat_n(input_line, completed));
+        self.tracked = output.len();
+    }
+
+    /// Records the rest of the output, an unterminated last line included, as coming from `input_line`.
+    pub(crate) fn finish(&mut self, output: &str, input_line: usize) {
+        self.track(output, input_line);
+        if !output.is_empty() && !output.ends_with('\n') {
+            self.lines.push(input_line);
+        }
+    }
+
+    /// Returns the input line that output line `line` comes from.
+    ///
+    /// Lines past the end of the map continue after the last mapped line.
+    pub(crate) fn input_line(&self, line: usize) -> usize {
+        match (self.lines.get(line), self.lines.last()) {
+            (Some(&input), _) => input,
+            (None, Some(&last)) => last + 1 + line - self.lines.len(),
+            (None, None) => line,
+        }
+    }
+
+    /// Chains this map after `earlier`, the map of the pass that produced this pass's input.
+    pub(crate) fn after(self, earlier: &LineMap) -> LineMap {
+        LineMap {
+            lines: self.lines.iter().map(|&line| earlier.input_line(line)).collect(),
+            tracked: 0,
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! Rewrites Docusaurus internal doc links so they point at converted `.qmd` files.
+//!
+//! Docusaurus links between docs usually target the source file directly, e.g.
+//! `[Intro](./intro.md)` or `[Guide](../guide/setup.mdx#install)`. Quarto resolves
+//! links against the rendered `.qmd` sources, so relative `.md`/`.mdx` targets are
+//! rewritten to `.qmd` while external URLs, pure anchors and images are left alone.
+
+use crate::fence::{FenceTracker, map_outside_inline_code, split_inline_code};
+use crate::index_pages;
+use log::warn;
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+
+static INLINE_LINK: OnceLock<Regex> = OnceLock::new();
+static REFERENCE_DEFINITION: OnceLock<Regex> = OnceLock::new();
+static SITE_ALIAS: OnceLock<Regex> = OnceLock::new();
+
+/// A relative `.qmd` link in the converted output whose target does not exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenLink {
+    /// 1-based line number in the converted content
+    pub line: usize,
+    /// Link target as written, e.g. `../guide/setup.qmd#install`
+    pub target: String,
+}
+
+/// Converts relative `.md`/`.mdx` link targets on a single line to `.qmd`.
+///
+/// Handles both inline links (`[text](./page.md)`) and reference-style link
+/// definitions (`[ref]: ./page.md`, `[ref]: <./my page.md> "Title"`). Footnote
+/// definitions (`[^1]: ...`) are treated as text, as are links inside inline code
+/// spans. Anchors and query strings are preserved.
+///
+/// # Arguments
+/// - `line`: A single line from the markdown file
+///
+/// # Returns
+/// The line with internal doc link targets rewritten, or unchanged if there are none
+pub fn convert_links(line: &str) -> String {
+    convert_links_with_extension(line, "qmd")
+}
+
+/// Converts relative `.md`/`.mdx` link targets on a single line to `extension`.
+///
+/// See `convert_links`, which is this function with the `qmd` extension.
+pub fn convert_links_with_extension(line: &str, extension: &str) -> String {
+    convert_links_with(line, |target| rewrite_link_target_with_extension(target, extension))
+}
+
+/// Converts link targets like `convert_links_with_extension`, pointing links to a
+/// README at the `index` page it is written as.
+///
+/// `source_dir` is the folder of the linking document, which the targets are
+/// resolved against. A README with an index page next to it keeps its name, as
+/// `index_pages::landing_page_path` does.
+pub(crate) fn convert_links_to_landing_pages(line: &str, extension: &str, source_dir: &Path) -> String {
+    convert_links_with(line, |target| {
+        let rewritten = rewrite_link_target_with_extension(target, extension)?;
+        let path = &target[..target.find(['#', '?']).unwrap_or(target.len())];
+        // The folder part keeps its trailing separator
+        let (folder, file_name) = path.split_at(path.rfind(['/', '\\']).map_or(0, |i| i + 1));
+        if !index_pages::is_readme(Path::new(file_name)) || index_pages::has_index_file(&source_dir.join(folder)) {
+            return Some(rewritten);
+        }
+        Some(format!("{}index.{}{}", folder.replace('\\', "/"), extension, &target[path.len()..]))
+    })
+}
+
+/// Rewrites the inline link and reference definition targets on a line with `rewrite`.
+fn convert_links_with(line: &str, rewrite: impl Fn(&str) -> Option<String>) -> String {
+    // Reference-style definitions take up the whole line
+    if let Some(caps) = reference_definition().captures(line) {
+        let (open, target, close) = split_angle_brackets(&caps[2]);
+        if let Some(target) = rewrite(target) {
+            return format!("{}{}{}{}{}", &caps[1], open, target, close, &caps[3]);
+        }
+        return line.to_string();
+    }
+
+    // Links shown as inline code are examples, not links
+    map_outside_inline_code(line, |_, text| {
+        inline_link()
+            .replace_all(text, |caps: &regex::Captures| {
+                // Leave image references untouched, their targets are not documents
+                if &caps[1] == "!" {
+                    return caps[0].to_string();
+                }
+                match rewrite(&caps[3]) {
+                    Some(target) => format!("[{}]({}{})", &caps[2], target, &caps[4]),
+                    None => caps[0].to_string(),
+                }
+            })
+            .into_owned()
+    })
+}
+
+/// Rewrites a single link target if it is a relative `.md`/`.mdx` document link.
+///
+/// Backslash separators (`..\guide\setup.md`, as written on Windows) are
+/// replaced with `/`, the only separator valid in a Quarto link.
+///
+/// # Returns
+/// - `Some(target)` with a `.qmd` extension if the target was rewritten
+/// - `None` for external URLs, anchors and non-document targets
+pub fn rewrite_link_target(target: &str) -> Option<String> {
+    rewrite_link_target_with_extension(target, "qmd")
+}
+
+/// Rewrites a relative `.md`/`.mdx` document link target to `extension`.
+///
+/// See `rewrite_link_target`, which is this function with the `qmd` extension.
+pub fn rewrite_link_target_with_extension(target: &str, extension: &str) -> Option<String> {
+    // External URLs (https://, mailto:, ...) and in-page anchors stay as they are
+    if target.starts_with('#') || target.contains("://") || target.starts_with("mailto:") {
+        return None;
+    }
+
+    // Split off any anchor or query string so only the path is inspected
+    let split_at = target.find(['#', '?']).unwrap_or(target.len());
+    let (path, suffix) = target.split_at(split_at);
+
+    let stem = path
+        .strip_suffix(".mdx")
+        .or_else(|| path.strip_suffix(".md"))?;
+
+    Some(format!("{}.{}{}", stem.replace('\\', "/"), extension, suffix))
+}
+
+/// Finds relative `.qmd` links in converted content whose targets do not resolve.
+///
+/// Links are recognized by the extension of `dest_file`, so output written with
+/// another extension (e.g. `.md`) is checked the same way.
+///
+/// Targets are resolved against the directory of `dest_file`. A target resolves
+/// if it is one of `known_outputs` (the files a tree conversion is producing) or
+/// already exists on disk. Image references, external URLs, absolute paths,
+/// fenced code blocks and inline code spans are ignored.
+///
+/// # Arguments
+/// - `content`: Converted content to scan
+/// - `dest_file`: Path the converted content is written to
+/// - `known_outputs`: Normalized destination paths of every converted file
+///
+/// # Returns
+/// One entry per dangling link, in document order
+pub fn find_broken_links(content: &str, dest_file: &Path, known_outputs: &BTreeSet<PathBuf>) -> Vec<BrokenLink> {
+    let dest_dir = dest_file.parent().unwrap_or(Path::new(""));
+    let extension = dest_file.extension().and_then(|e| e.to_str()).unwrap_or("qmd");
+    let mut broken = Vec::new();
+    let mut fences = FenceTracker::default();
+
+    for (index, line) in content.lines().enumerate() {
+        if fences.update(line) {
+            continue;
+        }
+
+        let mut targets = Vec::new();
+        if let Some(caps) = reference_definition().captures(line) {
+            targets.push(split_angle_brackets(caps.get(2).map_or("", |m| m.as_str())).1);
+        } else {
+            for (text, _) in split_inline_code(line).into_iter().filter(|(_, is_code)| !is_code) {
+                for caps in inline_link().captures_iter(text) {
+                    if &caps[1] != "!" {
+                        targets.push(caps.get(3).map_or("", |m| m.as_str()));
+                    }
+                }
+            }
+        }
+
+        for target in targets {
+            if target.contains("://") || target.starts_with('/') || target.starts_with('#') {
+                continue;
+            }
+            let path = &target[..target.find(['#', '?']).unwrap_or(target.len())];
+            if Path::new(path).extension().and_then(|e| e.to_str()) != Some(extension) {
+                continue;
+            }
+            let resolved = normalize_path(&dest_dir.join(path));
+            if !known_outputs.contains(&resolved) && !resolved.exists() {
+                broken.push(BrokenLink {
+                    line: index + 1,
+                    target: target.to_string(),
+                });
+            }
+        }
+    }
+
+    broken
+}
+
+/// Removes `.` components and folds `..` into the preceding component, without touching the filesystem.
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if matches!(normalized.components().next_back(), Some(Component::Normal(_))) => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Resolves Docusaurus `@site/` path aliases in link and image targets on a single line.
+///
+/// Docusaurus resolves `@site/static/img/foo.png` against the site root. The
+/// alias is replaced with `site_base` (e.g. `/` or `https://example.com/`); the
+/// rest of the path is kept. Targets inside Markdown link parentheses, reference
+/// definitions and quoted HTML attributes are handled, inline code is left alone.
+///
+/// `@site/` aliases without a configured base and `@docusaurus/` module aliases
+/// cannot be resolved; they are left unchanged with a warning.
+///
+/// # Arguments
+/// - `line`: A single line from the markdown file
+/// - `site_base`: Replacement for the `@site/` prefix, if configured
+///
+/// # Returns
+/// The line with resolvable aliases replaced
+pub fn resolve_site_aliases(line: &str, site_base: Option<&str>) -> String {
+    let (resolved, unresolved) = resolve_site_aliases_reporting(line, site_base);
+    for alias in unresolved {
+        warn!("  ⚠ Cannot resolve path alias `{}`, leaving it unchanged", alias);
+    }
+    resolved
+}
+
+/// Same as `resolve_site_aliases`, returning the aliases left unchanged instead of logging them.
+pub(crate) fn resolve_site_aliases_reporting(line: &str, site_base: Option<&str>) -> (String, Vec<String>) {
+    if !line.contains('@') {
+        return (line.to_string(), Vec::new());
+    }
+
+    let mut unresolved = Vec::new();
+    let resolved = map_outside_inline_code(line, |_, text| {
+        site_alias()
+            .replace_all(text, |caps: &regex::Captures| {
+                let (prefix, alias, path) = (&caps[1], &caps[2], &caps[3]);
+                match (alias, site_base) {
+                    ("site", Some(base)) => {
+                        format!("{}{}/{}", prefix, base.trim_end_matches('/'), path)
+                    }
+                    _ => {
+                        unresolved.push(format!("@{}/{}", alias, path));
+                        caps[0].to_string()
+                    }
+                }
+            })
+            .into_owned()
+    });
+    (resolved, unresolved)
+}
+
+fn site_alias() -> &'static Regex {
+    SITE_ALIAS.get_or_init(|| Regex::new(r#"(\]\(|\]:\s*|=["']?)@(site|docusaurus)/([^)\s"'>]*)"#).unwrap())
+}
+
+/// Splits the `<` `>` a reference definition target may be wrapped in off the target.
+///
+/// # Returns
+/// The opening bracket, the bare target and the closing bracket, the brackets
+/// being empty if the target is not wrapped
+fn split_angle_brackets(target: &str) -> (&str, &str, &str) {
+    match target.strip_prefix('<').and_then(|t| t.strip_suffix('>')) {
+        Some(inner) => ("<", inner, ">"),
+        None => ("", target, ""),
+    }
+}
+
+fn inline_link() -> &'static Regex {
+    INLINE_LINK.get_or_init(|| Regex::new(r#"(!?)\[([^\]]*)\]\(([^)\s]+)((?:\s+"[^"]*")?)\)"#).unwrap())
+}
+
+fn reference_definition() -> &'static Regex {
+    REFERENCE_DEFINITION.get_or_init(|| Regex::new(r"^(\s{0,3}\[[^\]^][^\]]*\]:\s*)(<[^>]*>|\S+)(.*)$").unwrap())
+}
@@ -2,9 +2,14 @@ use clap::Parser;
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
 use std::fs;
-use std::path::{PathBuf};
-use walkdir::WalkDir;
-use doc2quarto::{process_files};
+use std::io::{IsTerminal, Read};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::path::{Path, PathBuf};
+use doc2quarto::{
+    collect_filtered_source_files, convert_content_with_options, convert_file, count_warnings, convert_tree_with_progress, is_markdown_file, load_authors, plan_conversion, process_files, watch, CalloutTitleStyle, Config,
+    ConversionReport, ConvertOptions, DocId, ExistingOutput, format_warning_counts, ImageMode, InvalidUtf8, TruncateMarker, VersionedDocs, WatchUpdate,
+};
 
 #[derive(Parser, Debug)]
 #[command(version)]
@@ -12,27 +17,371 @@ use doc2quarto::{process_files};
 #[command(about="Converts markdown.md to Quarto .qmd format", long_about=None)]
 pub struct Args {
     
-    /// source directory containing Docusaurus markdown files
+    /// source directory containing Docusaurus markdown files, or a single .md/.mdx file
+    #[arg(short, long, conflicts_with = "stdin", required_unless_present = "stdin")]
+    pub source: Option<PathBuf>,
+
+    /// destination directory for converted Quarto files (or the output .qmd file for a single source file)
+    #[arg(short, long, conflicts_with = "stdin", required_unless_present = "stdin")]
+    pub dest: Option<PathBuf>,
+
+    /// directory that output paths mirror, when --source is only part of it (e.g. --source docs/guides --source-root docs)
+    #[arg(long, value_name = "DIR", conflicts_with = "stdin")]
+    pub source_root: Option<PathBuf>,
+
+    /// read one document from stdin and write its conversion to stdout, instead of --source/--dest
+    #[arg(long, visible_alias = "stdout")]
+    pub stdin: bool,
+
+    /// only convert plain .md files, skipping .mdx files
+    #[arg(long)]
+    pub no_mdx: bool,
+
+    /// preview the conversion without writing any files
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// print each source file and its destination path, then exit without converting anything
+    #[arg(long)]
+    pub list: bool,
+
+    /// print a unified diff between each existing destination file and its conversion, writing nothing
+    #[arg(long)]
+    pub diff: bool,
+
+    /// leave relative .md/.mdx link targets unchanged
+    #[arg(long)]
+    pub no_links: bool,
+
+    /// leave Docusaurus <Tabs> components unconverted
+    #[arg(long)]
+    pub no_tabs: bool,
+
+    /// keep README.md files as README.qmd instead of writing them as the folder's index.qmd
+    #[arg(long)]
+    pub keep_readme: bool,
+
+    /// do not copy image folders next to the source files
+    #[arg(long)]
+    pub no_images: bool,
+
+    /// name of an image folder to copy next to each file (repeatable, defaults to img, images, assets, _assets)
+    #[arg(long = "image-folder", value_name = "NAME")]
+    pub image_folders: Vec<String>,
+
+    /// convert legacy remark inline footnotes ([^note with spaces]) to Pandoc inline notes (^[note])
+    #[arg(long)]
+    pub normalize_footnotes: bool,
+
+    /// convert raw HTML <details>/<summary> blocks to collapsible Quarto callouts
+    #[arg(long)]
+    pub convert_details: bool,
+
+    /// octal permissions for copied images (default 644), or "keep" to keep the source permissions
+    #[arg(long, value_name = "MODE", value_parser = parse_image_mode)]
+    pub image_mode: Option<ImageMode>,
+
+    /// copy every image again, even when the earlier copy has the same size and modification time
+    #[arg(long)]
+    pub recopy_images: bool,
+
+    /// emit callout titles as title="..." attributes instead of headings
+    #[arg(long)]
+    pub title_attribute: bool,
+
+    /// convert files that are not valid UTF-8 by replacing invalid bytes instead of skipping them
+    #[arg(long)]
+    pub lossy_utf8: bool,
+
+    /// TOML or YAML config file with custom admonition types and default metadata
     #[arg(short, long)]
-   pub source: PathBuf,
+    pub config: Option<PathBuf>,
+
+    /// skip files whose destination .qmd already exists instead of overwriting them
+    #[arg(long, conflicts_with = "backup")]
+    pub no_clobber: bool,
+
+    /// rename existing destination files to .qmd.bak before overwriting them
+    #[arg(long)]
+    pub backup: bool,
+
+    /// set each converted file's modification time to that of its source
+    #[arg(long)]
+    pub preserve_mtime: bool,
+
+    /// keep \r\n line endings in files that mostly use them, instead of writing \n
+    #[arg(long)]
+    pub preserve_line_endings: bool,
+
+    /// remove Docusaurus <!-- truncate --> blog markers
+    #[arg(long)]
+    pub strip_truncate: bool,
+
+    /// remove <!-- truncate --> markers and use the text before them as the description
+    #[arg(long, conflicts_with = "strip_truncate")]
+    pub truncate_description: bool,
+
+    /// turn the Docusaurus `id` frontmatter field into an alias instead of dropping it
+    #[arg(long)]
+    pub id_alias: bool,
+
+    /// base path or URL that Docusaurus @site/ aliases resolve to (e.g. "/")
+    #[arg(long, value_name = "BASE")]
+    pub site_base: Option<String>,
+
+    /// Docusaurus blog authors.yml used to resolve `authors: [slug]` frontmatter
+    #[arg(long, value_name = "FILE")]
+    pub authors: Option<PathBuf>,
+
+    /// re-parse converted frontmatter and fail files that would be written with invalid YAML
+    #[arg(long)]
+    pub validate: bool,
+
+    /// only convert files whose path relative to the source matches this glob (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    pub include: Vec<String>,
+
+    /// skip files whose path relative to the source matches this glob (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// warn about relative links whose .qmd target is not produced by the conversion
+    #[arg(long)]
+    pub check_links: bool,
+
+    /// write every converted file into the destination root, prefixing colliding names with their parent directory
+    #[arg(long)]
+    pub flatten: bool,
+
+    /// where versioned_docs/version-<name>/ folders go: "keep" (default), "subdirs" (<name>/) or "profiles" (<name>/ plus a _quarto-<name>.yml profile)
+    #[arg(long, value_name = "LAYOUT", value_parser = parse_versioned_docs)]
+    pub versions: Option<VersionedDocs>,
+
+    /// extension of the converted files and rewritten doc links, e.g. md or Rmd
+    #[arg(long, visible_alias = "extension", value_name = "EXT", default_value = doc2quarto::DEFAULT_OUTPUT_EXTENSION, value_parser = parse_output_extension)]
+    pub output_extension: String,
+
+    /// skip files that are unchanged since the last run (tracked in a manifest in the destination)
+    #[arg(long)]
+    pub incremental: bool,
+
+    /// keep running after the conversion and reconvert source files as they change
+    #[arg(long)]
+    pub watch: bool,
+
+    /// fail the run if any unconverted MDX components remain in the output
+    #[arg(long)]
+    pub strict: bool,
+
+    /// stop at the first file that fails to convert instead of converting the rest
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// maximum number of files to convert in parallel (defaults to the number of CPUs)
+    #[arg(short, long)]
+    pub jobs: Option<usize>,
+
+    /// print a machine-readable JSON summary instead of the human-friendly output
+    #[arg(long)]
+    pub json: bool,
+
+    /// write the per-file report to this file, as CSV if it ends in .csv and JSON otherwise
+    #[arg(long, value_name = "PATH")]
+    pub report_file: Option<PathBuf>,
+
+    /// print per-file conversion details
+    #[arg(short, long, conflicts_with = "quiet")]
+    pub verbose: bool,
+
+    /// print plain "Processed N/M" lines instead of the progress bar (the default when stdout is not a terminal)
+    #[arg(long)]
+    pub no_progress: bool,
 
-    /// destination directory for converted Quarto files
+    /// only print errors and the final summary
     #[arg(short, long)]
-    pub dest: PathBuf,
+    pub quiet: bool,
 
 }
 
 
+/// Writes library log records to stderr, filtered by the level chosen on the command line.
+struct CliLogger;
+
+impl log::Log for CliLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("{}", record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CliLogger = CliLogger;
+
+
+/// Parses an octal permission mode such as `644`, or `keep`.
+fn parse_image_mode(value: &str) -> Result<ImageMode, String> {
+    if value == "keep" {
+        return Ok(ImageMode::Keep);
+    }
+    match u32::from_str_radix(value.trim_start_matches("0o"), 8) {
+        Ok(bits) if bits <= 0o7777 => Ok(ImageMode::Mode(bits)),
+        _ => Err(format!("expected an octal mode such as 644 or `keep`, got {:?}", value)),
+    }
+}
+
+/// Parses the `--versions` layout.
+fn parse_versioned_docs(value: &str) -> Result<VersionedDocs, String> {
+    match value {
+        "keep" => Ok(VersionedDocs::Keep),
+        "subdirs" => Ok(VersionedDocs::Subdirectories),
+        "profiles" => Ok(VersionedDocs::Profiles),
+        _ => Err(format!("expected `keep`, `subdirs` or `profiles`, got {:?}", value)),
+    }
+}
+
+/// Accepts `md` as well as `.md`, rejecting extensions with path separators.
+fn parse_output_extension(value: &str) -> Result<String, String> {
+    let extension = value.strip_prefix('.').unwrap_or(value);
+    doc2quarto::validate_output_extension(extension).map_err(|e| e.to_string())?;
+    Ok(extension.to_string())
+}
+
+/// Maps the command line flags (and the files they name) onto conversion options.
+///
+/// Exits the process if the config or authors file cannot be loaded.
+fn build_options(args: &Args) -> ConvertOptions {
+    let mut opts = ConvertOptions {
+        include_mdx: !args.no_mdx,
+        dry_run: args.dry_run || args.diff,
+        convert_links: !args.no_links,
+        convert_tabs: !args.no_tabs,
+        readme_as_index: !args.keep_readme,
+        callout_title_style: if args.title_attribute { CalloutTitleStyle::Attribute } else { CalloutTitleStyle::Heading },
+        copy_images: !args.no_images,
+        skip_unchanged_images: !args.recopy_images,
+        invalid_utf8: if args.lossy_utf8 { InvalidUtf8::Lossy } else { InvalidUtf8::Skip },
+        preserve_mtime: args.preserve_mtime,
+        preserve_line_endings: args.preserve_line_endings,
+        incremental: args.incremental,
+        site_base: args.site_base.clone(),
+        validate: args.validate,
+        relative_to: args.source_root.clone(),
+        include: args.include.clone(),
+        exclude: args.exclude.clone(),
+        check_links: args.check_links,
+        flatten: args.flatten,
+        versioned_docs: args.versions.unwrap_or_default(),
+        output_extension: args.output_extension.clone(),
+        fail_fast: args.fail_fast,
+        normalize_footnotes: args.normalize_footnotes,
+        convert_details: args.convert_details,
+        diff: args.diff,
+        doc_id: if args.id_alias { DocId::Alias } else { DocId::Drop },
+        truncate_marker: if args.truncate_description {
+            TruncateMarker::Description
+        } else if args.strip_truncate {
+            TruncateMarker::Remove
+        } else {
+            TruncateMarker::Keep
+        },
+        existing_output: if args.no_clobber {
+            ExistingOutput::Skip
+        } else if args.backup {
+            ExistingOutput::Backup
+        } else {
+            ExistingOutput::Overwrite
+        },
+        ..ConvertOptions::default()
+    };
+    if let Some(path) = &args.config {
+        match Config::load(path) {
+            Ok(config) => {
+                opts.admonition_types = config.admonitions;
+                opts.components = config.components;
+                opts.metadata = config.metadata;
+                if let Some(keys) = config.dropped_keys {
+                    opts.dropped_frontmatter_keys = keys;
+                }
+            }
+            Err(e) => {
+                eprintln!("{} Failed to load config: {}", "x".red(), e);
+                std::process::exit(1);
+            }
+        }
+    }
+    if !args.image_folders.is_empty() {
+        opts.image_folders = args.image_folders.clone();
+    }
+    if let Some(mode) = args.image_mode {
+        opts.image_mode = mode;
+    }
+    if let Some(path) = &args.authors {
+        match load_authors(path) {
+            Ok(authors) => opts.authors = authors,
+            Err(e) => {
+                eprintln!("{} Failed to load authors: {}", "x".red(), e);
+                std::process::exit(1);
+            }
+        }
+    }
+    opts
+}
+
 pub fn main() {
 
     let args = Args::parse();
-    println!("\n");
-    println!("{}","Doc2Quarto - Docusaurus to Quarto Converter".bright_cyan().bold());
-    println!("{}", "=".repeat(45).bright_black());
+
+    // Warnings by default, per-file detail with --verbose, errors only with --quiet
+    let level = if args.verbose {
+        log::LevelFilter::Debug
+    } else if args.quiet {
+        log::LevelFilter::Error
+    } else {
+        log::LevelFilter::Warn
+    };
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level);
+    }
+
+    let opts = build_options(&args);
+
+    // Piping mode skips all file handling, stdout only carries the converted document
+    if args.stdin {
+        let mut content = String::new();
+        if let Err(e) = std::io::stdin().read_to_string(&mut content) {
+            eprintln!("{} Failed to read stdin: {}", "x".red(), e);
+            std::process::exit(1);
+        }
+        print!("{}", convert_content_with_options(&content, &opts));
+        return;
+    }
+    let (Some(source), Some(dest)) = (args.source.clone(), args.dest.clone()) else {
+        unreachable!("clap requires --source and --dest without --stdin");
+    };
+
+    if args.list {
+        list_files(&source, &dest, &opts, args.json);
+        return;
+    }
+
+    // JSON mode keeps stdout free for the summary document
+    let human_output = !args.quiet && !args.json;
+
+    if human_output {
+        println!("\n");
+        println!("{}","Doc2Quarto - Docusaurus to Quarto Converter".bright_cyan().bold());
+        println!("{}", "=".repeat(45).bright_black());
+    }
 
     // check if Source directory if exists
-    if !args.source.exists() {
-        eprintln!("{} Source directory does not exists: {:?}", "x".red(), args.source);
+    if !source.exists() {
+        eprintln!("{} Source directory does not exists: {:?}", "x".red(), source);
         std::process::exit(1);
     }
 
@@ -48,65 +397,393 @@ pub fn main() {
     // }
 
 
+    // A single source file is converted on its own, --dest may then name the output file
+    let single_file = source.is_file();
+    if single_file && args.watch {
+        eprintln!("{} --watch needs a source directory", "x".red());
+        std::process::exit(1);
+    }
+
     // Create destination directory if it doesn't exist
-    if let Err(e) = fs::create_dir_all(&args.dest) {
+    if !single_file && !args.dry_run && !args.diff && let Err(e) = fs::create_dir_all(&dest) {
         eprintln!("{} Failed to create destination directory: {}", "✗".red(), e);
         std::process::exit(1);
     }
 
 
-    // collect all .md files from source director
+    // collect all .md (and .mdx unless disabled) files from source director
 
-    let md_files: Vec<PathBuf> = WalkDir::new(&args.source)
-                 .into_iter()
-                 .filter_map(|e| e.ok())
-                 .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("md"))
-                .map(|e| e.path().to_path_buf())           
-                .collect();
+    let (md_files, filtered_out) = if single_file {
+        if !is_markdown_file(&source, true) {
+            eprintln!("{} Source file is not a .md or .mdx file: {:?}", "x".red(), source);
+            std::process::exit(1);
+        }
+        (vec![source.clone()], 0)
+    } else {
+        match collect_filtered_source_files(&source, &opts) {
+            Ok(collected) => collected,
+            Err(e) => {
+                eprintln!("{} {}", "x".red(), e);
+                std::process::exit(1);
+            }
+        }
+    };
 
-    if md_files.len() == 0 {
-        eprintln!("{} No .md files found in source directory", "x".red());
+    if md_files.is_empty() {
+        eprintln!("{} No markdown files found in source directory", "x".red());
         std::process::exit(1);
     }
-    println!("{} Found {} .md files in source directory", "✓".green(), md_files.len());
-    println!("\n{} Found {} markdown files", "ℹ".blue(), md_files.len());
+    if human_output {
+        println!("{} Found {} markdown files in source directory", "✓".green(), md_files.len());
+        println!("\n{} Found {} markdown files", "ℹ".blue(), md_files.len());
+        if filtered_out > 0 {
+            println!("{} Skipped {} files by --include/--exclude filters", "ℹ".blue(), filtered_out);
+        }
+    }
+
+    // A progress bar turns into noise in CI logs, print plain progress lines there instead
+    let plain_progress = human_output && (args.no_progress || !std::io::stdout().is_terminal());
 
     //create progress bar for visual feedback
-    let pb = ProgressBar::new(md_files.len() as u64);
+    let pb = if !human_output || plain_progress { ProgressBar::hidden() } else { ProgressBar::new(md_files.len() as u64) };
     pb.set_style(
         ProgressStyle::default_bar()
-            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {post}/{len} {msg}")
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} {msg}")
             .unwrap()
             .progress_chars("#>-"),
     );
    
-    // let mut success_count = 0;
-    // let error_count = 0;
+    // Cap the worker threads if requested
+    if let Some(jobs) = args.jobs
+        && let Err(e) = rayon::ThreadPoolBuilder::new().num_threads(jobs).build_global()
+    {
+        eprintln!("{} Failed to configure {} worker threads: {}", "x".red(), jobs, e);
+        std::process::exit(1);
+    }
 
-    // Process each markdown file
-    for md_file in md_files {
-        let file_name = md_file.file_name().unwrap().to_string_lossy();
+    // Counted as files finish so the bar can report failures without waiting for the summary
+    let failed_files = AtomicUsize::new(0);
+    // Files done so far, locked while printing so plain progress lines never go backwards
+    let plain_done = Mutex::new(0usize);
+    let plain_step = (md_files.len() / 10).max(1);
+    let on_file = |report: &ConversionReport| {
+        let file_name = report.source_path.file_name().unwrap().to_string_lossy();
         pb.set_message(format!("Processing: {}", file_name));
 
-        match process_files(&md_file, &args.source, &args.dest) {
-            Ok(_) => {
-                // success_count += 1;
-                pb.println(format!("{} Processed: {}", "✓".green(), file_name));
+        match &report.error {
+            None if report.unchanged => {
+                pb.println(format!("{} Unchanged: {} (up to date)", "-".bright_black(), file_name));
+            }
+            None if report.skipped => {
+                pb.println(format!("{} Skipped: {} (destination exists)", "-".yellow(), file_name));
+            }
+            None => {
+                pb.println(format!("{} Processed: {} ({} bytes)", "✓".green(), file_name, report.bytes_written));
+            }
+            Some(e) => {
+                failed_files.fetch_add(1, Ordering::Relaxed);
+                eprintln!("{} Failed to process {}: {}", "x".red(), file_name, e);
             }
-            Err(e) => {
-                eprintln!("{} Failed to process file: {}", "x".red(), e);
-                pb.inc(1);
-            }   
         }
-   
+
+        // Exactly one step per file, whatever the outcome
         pb.inc(1);
 
+        if plain_progress {
+            let mut done = plain_done.lock().unwrap_or_else(|e| e.into_inner());
+            *done += 1;
+            if done.is_multiple_of(plain_step) || *done == md_files.len() {
+                println!("Processed {}/{} ({}%)", *done, md_files.len(), *done * 100 / md_files.len());
+            }
+        }
+    };
+
+    // Process markdown files in parallel; reports come back in path order
+    // so the summary is deterministic even though processing order is not
+    let reports = if single_file {
+        // With a root, the file lands at its path below the root as in a tree conversion
+        let report = match &args.source_root {
+            Some(root) => process_files(&source, root, &dest, &opts),
+            None => convert_file(&source, &dest, &opts),
+        }
+        .unwrap_or_else(|e| ConversionReport::failed(&source, e.to_string()));
+        on_file(&report);
+        Ok(vec![report])
+    } else {
+        convert_tree_with_progress(&source, &dest, &opts, on_file)
+    };
+
+    let reports = match reports {
+        Ok(reports) => reports,
+        Err(e) => {
+            eprintln!("{} Conversion failed: {}", "x".red(), e);
+            std::process::exit(1);
+        }
+    };
+
+    let error_count = failed_files.into_inner();
+    let residual_count: usize = reports.iter().map(|r| r.residual_components.len()).sum();
+    debug_assert_eq!(error_count, reports.iter().filter(|r| !r.is_success()).count());
+
+    debug_assert_eq!(pb.position(), reports.len() as u64, "progress bar must advance once per file");
+    let stopped_early = reports.len() < md_files.len();
+    let message = if stopped_early {
+        format!("Stopped at the first failure, {} file(s) not converted", md_files.len() - reports.len())
+    } else if args.diff {
+        "Diff completed, no files written".to_string()
+    } else if args.dry_run {
+        "Dry run completed, no files written".to_string()
+    } else if error_count > 0 {
+        format!("Conversion completed, {} file(s) failed", error_count)
+    } else {
+        "Conversion completed!".to_string()
+    };
+    if plain_progress {
+        println!("{}", message);
+    } else if stopped_early {
+        pb.abandon_with_message(message);
+    } else {
+        pb.finish_with_message(message);
     }
-    pb.finish_with_message("Conversion completed!");
 
-    // Display Summary
+    if args.diff && !args.json {
+        for diff in reports.iter().filter_map(|r| r.diff.as_deref()) {
+            print!("{}", diff);
+        }
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&json_summary(&reports)).unwrap());
+    } else {
+        print_summary(&reports);
+    }
+
+    if let Some(path) = &args.report_file
+        && let Err(e) = write_report_file(path, &reports)
+    {
+        eprintln!("{} Failed to write report {:?}: {}", "x".red(), path, e);
+        std::process::exit(1);
+    }
+
+    if args.watch {
+        run_watch(&source, &dest, &opts);
+    }
+
+    // Fail the run so CI pipelines notice broken files
+    if error_count > 0 || (args.strict && residual_count > 0) {
+        std::process::exit(1);
+    }
 
 } // end of function
 
 
+/// Prints the source → destination mapping of every file the conversion would write, then returns.
+fn list_files(source: &Path, dest: &Path, opts: &ConvertOptions, json: bool) {
+    let plan = match plan_conversion(source, dest, opts) {
+        Ok(plan) => plan,
+        Err(e) => {
+            eprintln!("{} {}", "x".red(), e);
+            std::process::exit(1);
+        }
+    };
+
+    if json {
+        let files: Vec<_> = plan.iter().map(|(source, dest)| serde_json::json!({ "source": source, "dest": dest })).collect();
+        println!("{}", serde_json::to_string_pretty(&files).unwrap());
+    } else {
+        for (source, dest) in &plan {
+            println!("{} → {}", source.display(), dest.display());
+        }
+    }
+}
+
+/// Reconverts source files as they change, printing one line per update, until interrupted.
+fn run_watch(source: &Path, dest: &Path, opts: &ConvertOptions) {
+    println!("\n{} Watching {:?} for changes (Ctrl+C to stop)", "👀".blue(), source);
+    let result = watch(source, dest, opts, |path, update| {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        match update {
+            Ok(WatchUpdate::Converted(report)) if report.skipped => {
+                println!("{} Skipped: {} (destination exists)", "-".yellow(), file_name);
+            }
+            Ok(WatchUpdate::Converted(report)) => {
+                println!("{} Reconverted: {} ({} bytes)", "✓".green(), file_name, report.bytes_written);
+            }
+            Ok(WatchUpdate::Removed(dest_path)) => {
+                println!("{} Removed: {}", "-".yellow(), dest_path.display());
+            }
+            Err(e) => {
+                eprintln!("{} Failed to process {}: {}", "x".red(), file_name, e);
+            }
+        }
+    });
+
+    if let Err(e) = result {
+        eprintln!("{} {}", "x".red(), e);
+        std::process::exit(1);
+    }
+}
+
+
+/// Prints the human-friendly end-of-run summary.
+fn print_summary(reports: &[ConversionReport]) {
+    let success_count = reports.iter().filter(|r| r.is_success()).count();
+    let error_count = reports.len() - success_count;
+    let unchanged_count = reports.iter().filter(|r| r.unchanged).count();
+    let skipped_count = reports.iter().filter(|r| r.skipped && !r.unchanged).count();
+    let residual_count: usize = reports.iter().map(|r| r.residual_components.len()).sum();
+
+    // Display Summary
+    println!("\n{}", "=".repeat(45).bright_black());
+    println!(
+        "{} {} converted, {} skipped, {} failed",
+        if error_count == 0 { "✓".green() } else { "x".red() },
+        success_count - skipped_count - unchanged_count,
+        skipped_count,
+        error_count
+    );
+    if unchanged_count > 0 {
+        println!("{} {} file(s) unchanged since the last run", "⏭".blue(), unchanged_count);
+    }
+
+    let images_copied: usize = reports.iter().map(|r| r.copied_images.len()).sum();
+    let images_skipped: usize = reports.iter().map(|r| r.images_skipped).sum();
+    if images_copied + images_skipped > 0 {
+        println!("{} {} image(s) copied, {} unchanged skipped", "🖼".blue(), images_copied, images_skipped);
+    }
+
+    // One line per run, the per-line detail is logged with --verbose
+    let warning_counts = count_warnings(reports.iter().flat_map(|r| &r.warnings));
+    if !warning_counts.is_empty() {
+        println!("{} Warnings: {}", "⚠".yellow(), format_warning_counts(&warning_counts));
+    }
+
+    if residual_count > 0 {
+        println!("{} {} unconverted MDX component(s) remain:", "⚠".yellow(), residual_count);
+        for report in reports {
+            for component in &report.residual_components {
+                println!("    {}:{} <{}>", report.dest_path.display(), component.line, component.name);
+            }
+        }
+    }
+
+    let broken_count: usize = reports.iter().map(|r| r.broken_links.len()).sum();
+    if broken_count > 0 {
+        println!("{} {} broken link(s):", "⚠".yellow(), broken_count);
+        for report in reports {
+            for link in &report.broken_links {
+                println!("    {}:{} {}", report.dest_path.display(), link.line, link.target);
+            }
+        }
+    }
+}
+
+
+/// Writes the per-file report for `--report-file`, as CSV when the path ends in `.csv` and as the `--json` summary otherwise.
+fn write_report_file(path: &Path, reports: &[ConversionReport]) -> std::io::Result<()> {
+    let is_csv = path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+    let content = if is_csv {
+        csv_report(reports)
+    } else {
+        format!("{}\n", serde_json::to_string_pretty(&json_summary(reports)).unwrap())
+    };
+    fs::write(path, content)
+}
+
+/// One CSV row per file: paths, status, sizes, error and the warnings joined by `; `.
+fn csv_report(reports: &[ConversionReport]) -> String {
+    let mut csv = String::from("source,dest,status,bytes_read,bytes_written,error,warnings\n");
+    for report in reports {
+        let status = if !report.is_success() {
+            "failed"
+        } else if report.unchanged {
+            "unchanged"
+        } else if report.skipped {
+            "skipped"
+        } else {
+            "converted"
+        };
+        let warnings: Vec<String> = report
+            .warnings
+            .iter()
+            .map(|w| match w.line {
+                Some(line) => format!("{} (line {}): {}", w.kind.as_str(), line, w.message),
+                None => format!("{}: {}", w.kind.as_str(), w.message),
+            })
+            .collect();
+        let row = [
+            report.source_path.display().to_string(),
+            report.dest_path.display().to_string(),
+            status.to_string(),
+            report.bytes_read.to_string(),
+            report.bytes_written.to_string(),
+            report.error.clone().unwrap_or_default(),
+            warnings.join("; "),
+        ];
+        csv.push_str(&row.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quotes a CSV field if it holds a comma, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Builds the `--json` summary document.
+fn json_summary(reports: &[ConversionReport]) -> serde_json::Value {
+    let files: Vec<serde_json::Value> = reports
+        .iter()
+        .map(|report| {
+            let warnings: Vec<serde_json::Value> = report
+                .warnings
+                .iter()
+                .map(|w| {
+                    serde_json::json!({
+                        "kind": w.kind.as_str(),
+                        "line": w.line,
+                        "message": w.message,
+                    })
+                })
+                .collect();
+
+            serde_json::json!({
+                "source": report.source_path,
+                "dest": report.dest_path,
+                "bytes_read": report.bytes_read,
+                "bytes_written": report.bytes_written,
+                "success": report.is_success(),
+                "skipped": report.skipped,
+                "unchanged": report.unchanged,
+                "images_copied": report.copied_images.len(),
+                "images_skipped": report.images_skipped,
+                "error": report.error,
+                "warnings": warnings,
+            })
+        })
+        .collect();
+
+    let success_count = reports.iter().filter(|r| r.is_success()).count();
+    let unchanged_count = reports.iter().filter(|r| r.unchanged).count();
+    let skipped_count = reports.iter().filter(|r| r.skipped && !r.unchanged).count();
+    let warning_counts: serde_json::Map<String, serde_json::Value> = count_warnings(reports.iter().flat_map(|r| &r.warnings))
+        .into_iter()
+        .map(|(kind, count)| (kind.as_str().to_string(), count.into()))
+        .collect();
+    serde_json::json!({
+        "converted": success_count - skipped_count - unchanged_count,
+        "skipped": skipped_count,
+        "unchanged": unchanged_count,
+        "failed": reports.len() - success_count,
+        "warning_counts": warning_counts,
+        "files": files,
+    })
+}
+
+
 
@@ -0,0 +1,82 @@
+//! Manifest of source hashes used by incremental conversion.
+//!
+//! The manifest lives in the destination root as `.doc2quarto-manifest.json`
+//! and maps each source path (relative to the source root, `/`-separated) to a
+//! hash of its content at the time it was last converted:
+//!
+//! ```json
+//! {
+//!   "files": {
+//!     "guide/intro.md": "9f86d081884c7d65"
+//!   }
+//! }
+//! ```
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name of the manifest written to the destination root.
+pub const MANIFEST_FILE: &str = ".doc2quarto-manifest.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct Manifest {
+    /// Content hash of each converted source, keyed by its relative path
+    #[serde(default)]
+    files: BTreeMap<String, String>,
+}
+
+impl Manifest {
+    /// Loads the manifest from `dest_root`, starting empty if it is missing or unreadable.
+    pub(crate) fn load(dest_root: &Path) -> Manifest {
+        let Ok(source) = fs::read_to_string(manifest_path(dest_root)) else {
+            return Manifest::default();
+        };
+        serde_json::from_str(&source).unwrap_or_else(|e| {
+            warn!("  ⚠ Ignoring unreadable manifest {:?}: {}", manifest_path(dest_root), e);
+            Manifest::default()
+        })
+    }
+
+    /// Writes the manifest to `dest_root` as JSON.
+    pub(crate) fn save(&self, dest_root: &Path) -> Result<(), std::io::Error> {
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        fs::create_dir_all(dest_root)?;
+        fs::write(manifest_path(dest_root), json)
+    }
+
+    /// Returns true if `key` was last converted from content with the given hash.
+    pub(crate) fn is_unchanged(&self, key: &str, hash: &str) -> bool {
+        self.files.get(key).is_some_and(|h| h == hash)
+    }
+
+    pub(crate) fn insert(&mut self, key: String, hash: String) {
+        self.files.insert(key, hash);
+    }
+}
+
+/// Builds the manifest key for a source file: its path relative to the source root.
+pub(crate) fn manifest_key(relative_path: &Path) -> String {
+    relative_path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Hashes file content with 64-bit FNV-1a, which is stable across Rust versions.
+pub(crate) fn content_hash(bytes: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+fn manifest_path(dest_root: &Path) -> PathBuf {
+    dest_root.join(MANIFEST_FILE)
+}
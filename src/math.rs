@@ -0,0 +1,49 @@
+//! Normalizes LaTeX math delimiters to the `$`/`$$` form Quarto renders.
+//!
+//! Docusaurus sites configured for KaTeX sometimes write inline math as
+//! `\(...\)` and display math as `\[...\]`. Quarto (through Pandoc) only
+//! recognizes `$...$` and `$$...$$`, which are left unchanged, as is any `$` in
+//! prose. Inline code spans are never touched; fenced code blocks are skipped
+//! by `convert_content`.
+
+use crate::fence::map_outside_inline_code;
+use regex::Regex;
+use std::sync::OnceLock;
+
+static INLINE_MATH: OnceLock<Regex> = OnceLock::new();
+
+/// Converts `\(...\)` and `\[...\]` math delimiters on a single line.
+///
+/// Inline math becomes `$...$` with the inner spacing trimmed, as Pandoc does not
+/// treat `$ x $` as math. Display math is only converted when `\[` and `\]` stand
+/// on their own lines or enclose the whole line, so escaped brackets in prose
+/// (`arr\[0\]`) are kept.
+///
+/// # Arguments
+/// - `line`: A single line from the markdown file
+///
+/// # Returns
+/// The line with math delimiters converted, or unchanged if there are none
+pub fn convert_math_delimiters(line: &str) -> String {
+    let trimmed = line.trim();
+    let indent = &line[..line.len() - line.trim_start().len()];
+    if trimmed == "\\[" || trimmed == "\\]" {
+        return format!("{}$$", indent);
+    }
+    if let Some(inner) = trimmed.strip_prefix("\\[").and_then(|rest| rest.strip_suffix("\\]")) {
+        return format!("{}$${}$$", indent, inner.trim());
+    }
+    if !line.contains("\\(") {
+        return line.to_string();
+    }
+
+    map_outside_inline_code(line, |_, text| {
+        inline_math()
+            .replace_all(text, |caps: &regex::Captures| format!("${}$", caps[1].trim()))
+            .into_owned()
+    })
+}
+
+fn inline_math() -> &'static Regex {
+    INLINE_MATH.get_or_init(|| Regex::new(r"\\\((.+?)\\\)").unwrap())
+}
@@ -0,0 +1,300 @@
+//! Handling for MDX-only syntax that has no meaning in Quarto.
+//!
+//! Docusaurus `.mdx` files usually start with ES module statements such as
+//! `import Tabs from '@theme/Tabs';`. Pandoc renders these as literal text, so
+//! they are removed from the converted output and reported back to the caller.
+//! JSX comments, `{/* ... */}`, would render literally as well and are removed.
+
+use crate::fence::{FenceTracker, split_inline_code};
+use crate::line_map::LineMap;
+use regex::Regex;
+use std::sync::OnceLock;
+
+static IMPORT_STATEMENT: OnceLock<Regex> = OnceLock::new();
+static EXPORT_STATEMENT: OnceLock<Regex> = OnceLock::new();
+static JSX_COMPONENT: OnceLock<Regex> = OnceLock::new();
+static JSX_COMMENT_START: OnceLock<Regex> = OnceLock::new();
+static JSX_COMMENT_END: OnceLock<Regex> = OnceLock::new();
+
+/// A JSX component left in the converted output that Quarto cannot render.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResidualComponent {
+    /// 1-based line number in the scanned content; a `ConversionReport` numbers
+    /// them by their line in the source file
+    pub line: usize,
+    /// Component name, e.g. `MyComponent` or `Foo.Bar`
+    pub name: String,
+}
+
+/// Finds capitalized JSX tags such as `<MyComponent/>` remaining in converted content.
+///
+/// HTML elements are lowercase and left to Pandoc, so only tags whose name starts
+/// with an uppercase letter are reported. Fenced code blocks and inline code
+/// spans are ignored.
+///
+/// # Arguments
+/// - `content`: Converted content to scan
+///
+/// # Returns
+/// One entry per opening or self-closing component tag, in document order
+pub fn find_jsx_components(content: &str) -> Vec<ResidualComponent> {
+    let mut found = Vec::new();
+    let mut fences = FenceTracker::default();
+
+    for (index, line) in content.lines().enumerate() {
+        if fences.update(line) {
+            continue;
+        }
+        for (text, _) in split_inline_code(line).into_iter().filter(|(_, is_code)| !is_code) {
+            for caps in jsx_component().captures_iter(text) {
+                found.push(ResidualComponent {
+                    line: index + 1,
+                    name: caps[1].to_string(),
+                });
+            }
+        }
+    }
+
+    found
+}
+
+/// Removes top-level MDX `import`/`export` statements from the content.
+///
+/// Only lines that look like real ES module statements are removed:
+/// `import X from '...'`, `import '...'`, multi-line `import { A, B } from '...'`
+/// and `export const/function/default ...` declarations (followed until their
+/// braces balance). Prose that merely starts with the word "import" and any
+/// statements inside fenced code blocks are preserved.
+///
+/// # Arguments
+/// - `content`: The complete content of the markdown file as a string
+///
+/// # Returns
+/// A tuple of the content without MDX statements and the removed statements,
+/// each joined back into a single string
+pub fn strip_mdx_statements(content: &str) -> (String, Vec<String>) {
+    let (result, removed, _) = strip_mdx_statements_mapped(content);
+    (result, removed)
+}
+
+/// Same as `strip_mdx_statements`, also returning which source line each output line comes from.
+pub(crate) fn strip_mdx_statements_mapped(content: &str) -> (String, Vec<String>, LineMap) {
+    let mut result = String::with_capacity(content.len());
+    let mut map = LineMap::default();
+    let mut removed: Vec<String> = Vec::new();
+    let mut current: Option<(String, StatementKind)> = None;
+    let mut fences = FenceTracker::default();
+    let mut last_index = 0;
+
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        // Whatever the previous line wrote comes from it
+        map.track(&result, index.saturating_sub(1));
+        last_index = index;
+        let trimmed = line.trim_end_matches(['\n', '\r']);
+
+        // Continue collecting a multi-line statement until it is complete
+        if let Some((mut statement, kind)) = current.take() {
+            statement.push('\n');
+            statement.push_str(trimmed);
+            if kind.is_complete(&statement) {
+                removed.push(statement);
+            } else {
+                current = Some((statement, kind));
+            }
+            continue;
+        }
+
+        // Never touch code samples, JS examples commonly contain imports
+        if fences.update(trimmed) {
+            result.push_str(line);
+            continue;
+        }
+
+        if let Some(kind) = statement_kind(trimmed) {
+            let statement = trimmed.to_string();
+            if kind.is_complete(&statement) {
+                removed.push(statement);
+            } else {
+                current = Some((statement, kind));
+            }
+            continue;
+        }
+
+        result.push_str(line);
+    }
+
+    // An unterminated statement at end of file is kept rather than silently lost
+    if let Some((statement, _)) = current {
+        result.push_str(&statement);
+        result.push('\n');
+    }
+
+    map.finish(&result, last_index);
+    (result, removed, map)
+}
+
+/// Removes MDX `{/* ... */}` comments, including ones spanning several lines.
+///
+/// A line left with nothing but whitespace once its comments are removed is
+/// dropped. HTML `<!-- -->` comments, Pandoc attributes such as `{#id}` and
+/// comments inside fenced code blocks or inline code spans are preserved, as is
+/// everything from a `{/*` that is never closed to the end of the file.
+///
+/// # Arguments
+/// - `content`: The complete content of the markdown file as a string
+///
+/// # Returns
+/// The content without JSX comments
+pub fn strip_jsx_comments(content: &str) -> String {
+    strip_jsx_comments_mapped(content).0
+}
+
+/// Same as `strip_jsx_comments`, also returning which source line each output line comes from.
+///
+/// The third value is the 0-based line of a comment that is never closed, if any.
+pub(crate) fn strip_jsx_comments_mapped(content: &str) -> (String, LineMap, Option<usize>) {
+    let (result, map, unclosed) = strip_jsx_comments_before(content, None);
+    match unclosed {
+        // Keep the unclosed comment and what follows rather than silently losing it
+        Some(line) => {
+            let (result, map, _) = strip_jsx_comments_before(content, Some(line));
+            (result, map, Some(line))
+        }
+        None => (result, map, None),
+    }
+}
+
+/// Strips JSX comments from the lines before `stop`, copying the rest unchanged.
+///
+/// Returns the line where a comment left open at the end of the content started.
+fn strip_jsx_comments_before(content: &str, stop: Option<usize>) -> (String, LineMap, Option<usize>) {
+    let mut result = String::with_capacity(content.len());
+    let mut map = LineMap::default();
+    let mut fences = FenceTracker::default();
+    let mut in_comment = false;
+    let mut comment_start = 0;
+    let mut last_index = 0;
+
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        // Whatever the previous line wrote comes from it
+        map.track(&result, index.saturating_sub(1));
+        last_index = index;
+        let trimmed = line.trim_end_matches(['\n', '\r']);
+
+        if stop.is_some_and(|stop| index >= stop) {
+            result.push_str(line);
+            continue;
+        }
+
+        // A commented-out code fence is not a code block
+        if !in_comment && fences.update(trimmed) {
+            result.push_str(line);
+            continue;
+        }
+
+        let (kept, still_open) = strip_line_comments(trimmed, in_comment);
+        let touched = in_comment || still_open || kept.len() != trimmed.len();
+        if still_open && !in_comment {
+            comment_start = index;
+        }
+        in_comment = still_open;
+        if !touched {
+            result.push_str(line);
+        } else if !kept.trim().is_empty() {
+            result.push_str(kept.trim_end());
+            result.push_str(&line[trimmed.len()..]);
+        }
+    }
+
+    map.finish(&result, last_index);
+    (result, map, in_comment.then_some(comment_start))
+}
+
+/// Removes the JSX comments on one line, `in_comment` telling whether the line starts inside one.
+///
+/// Returns the kept text and whether a comment is still open at the end of the line.
+fn strip_line_comments(line: &str, mut in_comment: bool) -> (String, bool) {
+    let mut kept = String::new();
+    let mut rest = line;
+    loop {
+        if in_comment {
+            let Some(end) = jsx_comment_end().find(rest) else {
+                return (kept, true);
+            };
+            rest = &rest[end.end()..];
+        }
+        let Some((start, end)) = find_comment_start(rest) else {
+            kept.push_str(rest);
+            return (kept, false);
+        };
+        kept.push_str(&rest[..start]);
+        rest = &rest[end..];
+        in_comment = true;
+    }
+}
+
+/// Returns the byte range of the first `{/*` outside inline code spans.
+fn find_comment_start(text: &str) -> Option<(usize, usize)> {
+    let mut offset = 0;
+    for (piece, is_code) in split_inline_code(text) {
+        if !is_code && let Some(found) = jsx_comment_start().find(piece) {
+            return Some((offset + found.start(), offset + found.end()));
+        }
+        offset += piece.len();
+    }
+    None
+}
+
+#[derive(Clone, Copy)]
+enum StatementKind {
+    Import,
+    Export,
+}
+
+impl StatementKind {
+    /// Returns true once the collected statement text forms a complete statement.
+    fn is_complete(self, statement: &str) -> bool {
+        match self {
+            StatementKind::Import => import_statement().is_match(&statement.replace('\n', " ")),
+            StatementKind::Export => {
+                statement.matches('{').count() <= statement.matches('}').count()
+                    && statement.matches('(').count() <= statement.matches(')').count()
+            }
+        }
+    }
+}
+
+/// Detects whether a line begins an MDX module statement.
+fn statement_kind(line: &str) -> Option<StatementKind> {
+    if import_statement().is_match(line) || line.starts_with("import {") {
+        Some(StatementKind::Import)
+    } else if export_statement().is_match(line) {
+        Some(StatementKind::Export)
+    } else {
+        None
+    }
+}
+
+fn jsx_component() -> &'static Regex {
+    JSX_COMPONENT.get_or_init(|| Regex::new(r"<([A-Z][\w.]*)(?:\s|/|>|$)").unwrap())
+}
+
+fn jsx_comment_start() -> &'static Regex {
+    JSX_COMMENT_START.get_or_init(|| Regex::new(r"\{\s*/\*").unwrap())
+}
+
+fn jsx_comment_end() -> &'static Regex {
+    JSX_COMMENT_END.get_or_init(|| Regex::new(r"\*/\s*\}").unwrap())
+}
+
+fn import_statement() -> &'static Regex {
+    IMPORT_STATEMENT.get_or_init(|| {
+        Regex::new(r#"^import\s+(?:(?:[\w*{}\s,]+|\*\s+as\s+\w+)\s+from\s+)?['"][^'"]+['"];?\s*$"#).unwrap()
+    })
+}
+
+fn export_statement() -> &'static Regex {
+    EXPORT_STATEMENT.get_or_init(|| {
+        Regex::new(r"^export\s+(?:default\s|const\s|let\s|var\s|function\s|async\s+function\s|\{)").unwrap()
+    })
+}
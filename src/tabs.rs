@@ -0,0 +1,199 @@
+//! Converts Docusaurus `<Tabs>`/`<TabItem>` MDX components to Quarto tabsets.
+//!
+//! ```text
+//! <Tabs>                               ::: {.panel-tabset}
+//!   <TabItem value="a" label="Apple">  ## Apple
+//!     Apple content          →          Apple content
+//!   </TabItem>                          :::
+//! </Tabs>
+//! ```
+//!
+//! A `groupId` becomes the tabset's `group` attribute, so tabsets sharing it
+//! switch together. The `values` and `defaultValue` props have no Quarto
+//! equivalent and are ignored.
+
+use crate::fence::FenceTracker;
+use crate::line_map::LineMap;
+use regex::Regex;
+use std::sync::OnceLock;
+
+static TABS_OPEN: OnceLock<Regex> = OnceLock::new();
+static TAB_ITEM_OPEN: OnceLock<Regex> = OnceLock::new();
+static STRING_PROP: OnceLock<Regex> = OnceLock::new();
+
+/// Converts every `<Tabs>` block in the content to a Quarto `panel-tabset`.
+///
+/// Each `<TabItem>` becomes a `## label` heading (falling back to its `value`
+/// when no label is given). Tab content is dedented so indentation used for JSX
+/// readability is not mistaken for an indented code block. Lines inside fenced
+/// code blocks are never converted.
+///
+/// # Arguments
+/// - `content`: The complete content of the markdown file as a string
+///
+/// # Returns
+/// The content with tab components replaced by Quarto tabset markup
+pub fn convert_tabs(content: &str) -> String {
+    convert_tabs_mapped(content).0
+}
+
+/// Same as `convert_tabs`, also returning which source line each output line comes from.
+pub(crate) fn convert_tabs_mapped(content: &str) -> (String, LineMap) {
+    let mut result = String::with_capacity(content.len());
+    let mut map = LineMap::default();
+    let mut fences = FenceTracker::default();
+    let mut depth = 0usize;
+    // Buffered (line index, line) of the tab currently being read, dedented on close
+    let mut tab_lines: Option<Vec<(usize, &str)>> = None;
+    // Props read so far of a `<Tabs` opening tag that spans several lines
+    let mut open_tag_props: Option<String> = None;
+    let mut last_index = 0;
+
+    for (index, line) in content.lines().enumerate() {
+        // Whatever the previous line wrote comes from it
+        map.track(&result, index.saturating_sub(1));
+        last_index = index;
+        let trimmed = line.trim();
+
+        if let Some(mut props) = open_tag_props.take() {
+            props.push(' ');
+            props.push_str(trimmed);
+            if trimmed.ends_with('>') {
+                push_tabset_open(&mut result, &props);
+            } else {
+                open_tag_props = Some(props);
+            }
+            continue;
+        }
+
+        let in_code = fences.update(line);
+
+        if !in_code && tabs_open().is_match(trimmed) {
+            flush_tab(&mut result, &mut tab_lines, &mut map);
+            depth += 1;
+            if trimmed.ends_with('>') {
+                push_tabset_open(&mut result, trimmed);
+            } else {
+                open_tag_props = Some(trimmed.to_string());
+            }
+            continue;
+        }
+
+        if !in_code && depth > 0 && trimmed == "</Tabs>" {
+            flush_tab(&mut result, &mut tab_lines, &mut map);
+            depth -= 1;
+            result.push_str(":::\n");
+            continue;
+        }
+
+        if !in_code && depth > 0 {
+            if let Some(caps) = tab_item_open().captures(trimmed) {
+                flush_tab(&mut result, &mut tab_lines, &mut map);
+                let props = caps.get(1).map(|m| m.as_str()).unwrap_or("");
+                let label = prop(props, "label")
+                    .or_else(|| prop(props, "value"))
+                    .unwrap_or_else(|| "Tab".to_string());
+                result.push_str(&format!("## {}\n\n", label));
+
+                // Single-line form: `<TabItem value="a">content</TabItem>`
+                let rest = caps.get(2).map(|m| m.as_str()).unwrap_or("");
+                match rest.strip_suffix("</TabItem>") {
+                    Some(inline) => {
+                        if !inline.trim().is_empty() {
+                            result.push_str(inline.trim());
+                            result.push_str("\n\n");
+                        }
+                    }
+                    None => {
+                        let mut lines = Vec::new();
+                        if !rest.trim().is_empty() {
+                            lines.push((index, rest));
+                        }
+                        tab_lines = Some(lines);
+                    }
+                }
+                continue;
+            }
+
+            if trimmed == "</TabItem>" {
+                flush_tab(&mut result, &mut tab_lines, &mut map);
+                continue;
+            }
+        }
+
+        match tab_lines.as_mut() {
+            Some(lines) => lines.push((index, line)),
+            None => {
+                result.push_str(line);
+                result.push('\n');
+            }
+        }
+    }
+
+    flush_tab(&mut result, &mut tab_lines, &mut map);
+    map.finish(&result, last_index);
+    (result, map)
+}
+
+/// Writes the opening of a tabset; a `groupId` links it to the other tabsets in its group.
+fn push_tabset_open(result: &mut String, props: &str) {
+    match prop(props, "groupId") {
+        Some(group) => result.push_str(&format!("::: {{.panel-tabset group=\"{}\"}}\n\n", group)),
+        None => result.push_str("::: {.panel-tabset}\n\n"),
+    }
+}
+
+/// Writes the buffered tab body with its common indentation removed.
+fn flush_tab(result: &mut String, tab_lines: &mut Option<Vec<(usize, &str)>>, map: &mut LineMap) {
+    let Some(lines) = tab_lines.take() else {
+        return;
+    };
+
+    // Counted in characters, the indentation may hold multi-byte whitespace such as U+00A0
+    let indent = lines
+        .iter()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(_, l)| l.chars().take_while(|c| c.is_whitespace()).count())
+        .min()
+        .unwrap_or(0);
+
+    // Trim surrounding blank lines, the tabset markup adds its own spacing
+    let body: Vec<(usize, &str)> = lines
+        .iter()
+        .map(|&(index, l)| {
+            let stripped: usize = l.chars().take_while(|c| c.is_whitespace()).take(indent).map(char::len_utf8).sum();
+            (index, &l[stripped..])
+        })
+        .collect();
+    let start = body.iter().position(|(_, l)| !l.trim().is_empty());
+    let end = body.iter().rposition(|(_, l)| !l.trim().is_empty());
+
+    if let (Some(start), Some(end)) = (start, end) {
+        for &(index, line) in &body[start..=end] {
+            result.push_str(line);
+            result.push('\n');
+            map.track(result, index);
+        }
+        result.push('\n');
+    }
+}
+
+/// Reads a string-valued JSX prop (`name="x"`, `name='x'` or `name={"x"}`).
+pub(crate) fn prop(props: &str, name: &str) -> Option<String> {
+    string_prop()
+        .captures_iter(props)
+        .find(|caps| &caps[1] == name)
+        .and_then(|caps| caps.get(2).or_else(|| caps.get(3)).map(|m| m.as_str().to_string()))
+}
+
+fn tabs_open() -> &'static Regex {
+    TABS_OPEN.get_or_init(|| Regex::new(r"^<Tabs(\s[^>]*)?(>|$)").unwrap())
+}
+
+fn tab_item_open() -> &'static Regex {
+    TAB_ITEM_OPEN.get_or_init(|| Regex::new(r"^<TabItem(\s[^>]*)?>(.*)$").unwrap())
+}
+
+fn string_prop() -> &'static Regex {
+    STRING_PROP.get_or_init(|| Regex::new(r#"\b([\w-]+)=\{?(?:"([^"]*)"|'([^']*)')\}?"#).unwrap())
+}
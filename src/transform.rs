@@ -0,0 +1,87 @@
+//! User-registered line transformations for project-specific Docusaurus conventions.
+//!
+//! Transforms run on the raw source, one line at a time, before any built-in
+//! conversion pass (frontmatter, MDX statements, tabs, admonitions, links, ...):
+//!
+//! - transforms run in the order they were registered
+//! - each transform sees the line as left by the previous one
+//! - returning `None` leaves the line unchanged, `Some(text)` replaces it; the
+//!   replacement may span several lines, later transforms see it as one line
+//! - every line is passed, including frontmatter and fenced code blocks
+//!
+//! The built-in passes then convert the transformed content as if it were the source.
+
+use crate::line_map::LineMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// A custom transformation applied to each source line.
+pub type LineTransform = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+/// The line transforms of a conversion, in registration order.
+///
+/// Cloning shares the registered closures. Two sets compare equal when they
+/// hold the same closures in the same order.
+#[derive(Clone, Default)]
+pub struct LineTransforms {
+    transforms: Vec<Arc<LineTransform>>,
+}
+
+impl LineTransforms {
+    /// Registers a transform to run after those already registered.
+    pub fn push<F>(&mut self, transform: F)
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.transforms.push(Arc::new(transform));
+    }
+
+    /// Returns `true` if no transform is registered.
+    pub fn is_empty(&self) -> bool {
+        self.transforms.is_empty()
+    }
+
+    /// Runs every transform over each line of `content`.
+    ///
+    /// # Returns
+    /// The transformed content, keeping whether it ended with a newline
+    pub fn apply(&self, content: &str) -> String {
+        self.apply_mapped(content).0
+    }
+
+    /// Same as `apply`, also returning which source line each output line comes from.
+    pub(crate) fn apply_mapped(&self, content: &str) -> (String, LineMap) {
+        let mut result = String::with_capacity(content.len());
+        let mut map = LineMap::default();
+        for (index, line) in content.lines().enumerate() {
+            let mut line = line.to_string();
+            for transform in &self.transforms {
+                if let Some(replacement) = transform(&line) {
+                    line = replacement;
+                }
+            }
+            result.push_str(&line);
+            result.push('\n');
+            map.track(&result, index);
+        }
+        if !content.ends_with('\n') {
+            result.pop();
+        }
+        (result, map)
+    }
+}
+
+impl fmt::Debug for LineTransforms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LineTransforms").field("len", &self.transforms.len()).finish()
+    }
+}
+
+impl PartialEq for LineTransforms {
+    fn eq(&self, other: &Self) -> bool {
+        self.transforms.len() == other.transforms.len()
+            && self.transforms.iter().zip(&other.transforms).all(|(a, b)| Arc::ptr_eq(a, b))
+    }
+}
+
+impl Eq for LineTransforms {}
@@ -0,0 +1,89 @@
+//! Output layout for Docusaurus versioned docs.
+//!
+//! A versioned Docusaurus site keeps the current docs in `docs/` and a frozen
+//! copy of every released version in `versioned_docs/version-<name>/`. With the
+//! source root at the site root, `VersionedDocs` decides where those copies go:
+//!
+//! ```text
+//! versioned_docs/version-1.0/intro.md
+//!   Keep            → versioned_docs/version-1.0/intro.qmd
+//!   Subdirectories  → 1.0/intro.qmd
+//!   Profiles        → 1.0/intro.qmd, plus a _quarto-1.0.yml profile rendering 1.0/
+//! ```
+//!
+//! Only a `version-*` folder directly inside `versioned_docs` at the top of the
+//! source root is a version, so a `version-2/` folder elsewhere in the docs
+//! keeps its path.
+
+use crate::ExistingOutput;
+use log::{info, warn};
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Component, Path};
+
+/// Folder of a Docusaurus site holding the docs of released versions.
+pub const VERSIONED_DOCS_DIR: &str = "versioned_docs";
+
+/// Prefix of each version folder inside `versioned_docs`.
+const VERSION_PREFIX: &str = "version-";
+
+/// Where the docs of released versions are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VersionedDocs {
+    /// Mirror the source tree, `versioned_docs/version-1.0/` included
+    #[default]
+    Keep,
+    /// Write each version to a subdirectory named after it (`1.0/`) next to the current docs
+    Subdirectories,
+    /// Write versions as with `Subdirectories`, plus a Quarto profile per version
+    /// (`_quarto-1.0.yml`) so `quarto render --profile 1.0` renders only that version
+    Profiles,
+}
+
+/// Returns the version name and the path inside the version, if `relative_path` is in a versioned docs folder.
+///
+/// `relative_path` is relative to the source root, e.g. `versioned_docs/version-1.0/guide/intro.md`
+/// gives `("1.0", "guide/intro.md")`.
+pub(crate) fn split_version(relative_path: &Path) -> Option<(&str, &Path)> {
+    let mut components = relative_path.components();
+    if components.next() != Some(Component::Normal(VERSIONED_DOCS_DIR.as_ref())) {
+        return None;
+    }
+    let Some(Component::Normal(folder)) = components.next() else {
+        return None;
+    };
+    let version = folder.to_str()?.strip_prefix(VERSION_PREFIX).filter(|version| !version.is_empty())?;
+    Some((version, components.as_path()))
+}
+
+/// Returns the path below the destination root that a source path is written to, before the extension changes.
+pub(crate) fn output_relative_path(relative_path: &Path, mode: VersionedDocs) -> Cow<'_, Path> {
+    if mode == VersionedDocs::Keep {
+        return Cow::Borrowed(relative_path);
+    }
+    match split_version(relative_path) {
+        Some((version, rest)) => Cow::Owned(Path::new(version).join(rest)),
+        None => Cow::Borrowed(relative_path),
+    }
+}
+
+/// Writes a `_quarto-<version>.yml` profile to `dest_root` for every version among `relative_paths`.
+///
+/// Each profile limits rendering to the version's folder. Existing profiles
+/// are left alone with `ExistingOutput::Skip`; failures are logged, not fatal.
+pub(crate) fn write_profiles(relative_paths: &[&Path], dest_root: &Path, extension: &str, existing_output: ExistingOutput) {
+    let versions: BTreeSet<&str> = relative_paths.iter().filter_map(|path| split_version(path)).map(|(version, _)| version).collect();
+
+    for version in versions {
+        let profile = dest_root.join(format!("_quarto-{}.yml", version));
+        if profile.exists() && existing_output == ExistingOutput::Skip {
+            continue;
+        }
+        let content = format!("project:\n  render:\n    - \"{}/**/*.{}\"\n", version, extension);
+        match fs::write(&profile, content) {
+            Ok(()) => info!("  🏷 Wrote Quarto profile {:?} for version {}", profile, version),
+            Err(e) => warn!("  ⚠ Failed to write Quarto profile {:?}: {}", profile, e),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Structured warnings collected while converting content.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// What a conversion warning is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WarningKind {
+    /// An admonition type with no built-in or configured callout mapping
+    UnknownAdmonition,
+    /// An indented admonition, which Quarto only renders inside a list item
+    IndentedAdmonition,
+    /// A closing fence whose colon count differs from its opening
+    MismatchedFence,
+    /// A closing fence with no open admonition
+    UnmatchedFence,
+    /// Admonitions still open at the end of the file
+    UnclosedAdmonition,
+    /// A frontmatter block that is never closed
+    UnclosedFrontmatter,
+    /// A JSX `{/*` comment that is never closed
+    UnclosedComment,
+    /// A relative `.md`/`.mdx` link target rewritten to `.qmd`
+    RewrittenLink,
+    /// A JSX component left in the converted output
+    ResidualComponent,
+    /// A relative link whose target is not produced by the conversion
+    BrokenLink,
+    /// A `@site/` or `@docusaurus/` path alias that cannot be resolved
+    UnresolvedAlias,
+}
+
+impl WarningKind {
+    /// Stable snake_case name, as used in the `--json` summary.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WarningKind::UnknownAdmonition => "unknown_admonition",
+            WarningKind::IndentedAdmonition => "indented_admonition",
+            WarningKind::MismatchedFence => "mismatched_fence",
+            WarningKind::UnmatchedFence => "unmatched_fence",
+            WarningKind::UnclosedAdmonition => "unclosed_admonition",
+            WarningKind::UnclosedFrontmatter => "unclosed_frontmatter",
+            WarningKind::UnclosedComment => "unclosed_comment",
+            WarningKind::RewrittenLink => "rewritten_link",
+            WarningKind::ResidualComponent => "residual_component",
+            WarningKind::BrokenLink => "broken_link",
+            WarningKind::UnresolvedAlias => "unresolved_alias",
+        }
+    }
+
+    /// What a number of warnings of this kind are called in the end-of-run summary.
+    fn label(self) -> &'static str {
+        match self {
+            WarningKind::UnknownAdmonition => "unknown admonition type(s)",
+            WarningKind::IndentedAdmonition => "indented admonition(s)",
+            WarningKind::MismatchedFence => "mismatched closing fence(s)",
+            WarningKind::UnmatchedFence => "unmatched closing fence(s)",
+            WarningKind::UnclosedAdmonition => "file(s) with unclosed admonitions",
+            WarningKind::UnclosedFrontmatter => "unclosed frontmatter block(s)",
+            WarningKind::UnclosedComment => "unclosed JSX comment(s)",
+            WarningKind::RewrittenLink => "rewritten link line(s)",
+            WarningKind::ResidualComponent => "residual component(s)",
+            WarningKind::BrokenLink => "broken link(s)",
+            WarningKind::UnresolvedAlias => "unresolved path alias(es)",
+        }
+    }
+}
+
+/// A problem (or notable rewrite) found while converting content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// 1-based line the warning refers to, `None` for whole-file warnings.
+    ///
+    /// Broken links are numbered in the converted output, everything else in
+    /// the source, before MDX statements, comments, tabs and components are rewritten.
+    pub line: Option<usize>,
+    /// What the warning is about
+    pub kind: WarningKind,
+    /// Human-readable description, without the line number
+    pub message: String,
+}
+
+impl Warning {
+    pub(crate) fn new(line: Option<usize>, kind: WarningKind, message: String) -> Warning {
+        Warning { line, kind, message }
+    }
+}
+
+/// Counts warnings by kind.
+pub fn count_warnings<'a, I>(warnings: I) -> BTreeMap<WarningKind, usize>
+where
+    I: IntoIterator<Item = &'a Warning>,
+{
+    let mut counts = BTreeMap::new();
+    for warning in warnings {
+        *counts.entry(warning.kind).or_default() += 1;
+    }
+    counts
+}
+
+/// Formats warning counts as one line, e.g. `3 unknown admonition type(s), 5 residual component(s)`.
+pub fn format_warning_counts(counts: &BTreeMap<WarningKind, usize>) -> String {
+    counts
+        .iter()
+        .map(|(kind, count)| format!("{} {}", count, kind.label()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "Line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
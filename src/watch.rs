@@ -0,0 +1,129 @@
+//! Watch mode: reconverts source files as they change.
+//!
+//! File system events are debounced, so an editor saving a file in several
+//! steps triggers a single reconversion. Deleting a source file removes its
+//! converted `.qmd`.
+
+use crate::{
+    is_markdown_file, planned_dest_path, process_planned, resolve_relative_root, strip_source_root, ConversionError, ConversionReport,
+    ConvertOptions, SourceFilter,
+};
+use log::debug;
+use notify::{RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Quiet period after the last event before changed files are reconverted.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// What `apply_change` did for a changed source path.
+#[derive(Debug)]
+pub enum WatchUpdate {
+    /// The source file was (re)converted
+    Converted(ConversionReport),
+    /// The source file was deleted, so its converted output was removed
+    Removed(PathBuf),
+}
+
+/// Brings the output for one changed source path up to date.
+///
+/// Existing files are converted to the destination a tree conversion would
+/// give them; deleted files have their `.qmd` removed. Paths that are not source documents, or that the
+/// include/exclude globs in `opts` leave out, are ignored.
+///
+/// # Arguments
+/// - `changed`: Path reported as changed, below `source_root`
+/// - `source_root`: Root directory of the Docusaurus docs, or the watched part of
+///   `opts.relative_to`
+/// - `dest_root`: Root directory where converted files are written
+/// - `opts`: Conversion options
+///
+/// # Returns
+/// - `Ok(Some(update))` describing what was done
+/// - `Ok(None)` if the path is not a source document to convert
+/// - `Err` if converting the file or removing its output fails
+pub fn apply_change(changed: &Path, source_root: &Path, dest_root: &Path, opts: &ConvertOptions) -> Result<Option<WatchUpdate>, ConversionError> {
+    let (source_dir, source_root) = resolve_relative_root(source_root, opts)?;
+    let source_root: &Path = &source_root;
+    if !changed.starts_with(&source_dir) {
+        return Ok(None);
+    }
+    let Ok(relative_path) = strip_source_root(changed, source_root) else {
+        return Ok(None);
+    };
+    if !is_markdown_file(changed, opts.include_mdx) || !SourceFilter::new(&opts.include, &opts.exclude)?.matches(relative_path) {
+        return Ok(None);
+    }
+
+    // The same destination a full conversion picks, flattened names included
+    let dest_path = planned_dest_path(changed, &source_dir, source_root, dest_root, opts)?;
+    if changed.is_file() {
+        return process_planned(changed, &dest_path, dest_root, opts).map(|report| Some(WatchUpdate::Converted(report)));
+    }
+
+    if !dest_path.exists() {
+        return Ok(None);
+    }
+    if !opts.dry_run {
+        fs::remove_file(&dest_path).map_err(|source| ConversionError::WriteFailed { path: dest_path.clone(), source })?;
+    }
+    Ok(Some(WatchUpdate::Removed(dest_path)))
+}
+
+/// Watches `source_root` and reconverts changed source files until the watcher stops.
+///
+/// `on_update` is called with the changed path and the outcome of `apply_change`
+/// for every path that needed work.
+///
+/// # Errors
+/// Returns `ConversionError::WatchFailed` if the file system cannot be watched.
+pub fn watch<F>(source_root: &Path, dest_root: &Path, opts: &ConvertOptions, mut on_update: F) -> Result<(), ConversionError>
+where
+    F: FnMut(&Path, Result<WatchUpdate, ConversionError>),
+{
+    // Events carry absolute paths, so resolve the root the same way
+    let source_root = source_root
+        .canonicalize()
+        .map_err(|_| ConversionError::SourceNotFound { path: source_root.to_path_buf() })?;
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(&source_root, RecursiveMode::Recursive)?;
+
+    loop {
+        // Block for the first event, then collect everything until things go quiet
+        let mut changed = BTreeSet::new();
+        let Ok(event) = rx.recv() else {
+            return Ok(());
+        };
+        collect_changed_paths(&mut changed, event?);
+        while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+            collect_changed_paths(&mut changed, event?);
+        }
+        if changed.is_empty() {
+            continue;
+        }
+        debug!("  👀 {} path(s) changed", changed.len());
+
+        for path in changed {
+            match apply_change(&path, &source_root, dest_root, opts) {
+                Ok(Some(update)) => on_update(&path, Ok(update)),
+                Ok(None) => {}
+                Err(e) => on_update(&path, Err(e)),
+            }
+        }
+    }
+}
+
+/// Adds the paths of an event that changed something to `changed`.
+///
+/// Access events are skipped, otherwise reading a file to convert it would
+/// trigger its own reconversion.
+fn collect_changed_paths(changed: &mut BTreeSet<PathBuf>, event: notify::Event) {
+    if !event.kind.is_access() {
+        changed.extend(event.paths);
+    }
+}
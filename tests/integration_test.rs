@@ -1,5 +1,9 @@
 use doc2quarto::*;
 
+fn dry_run() -> ConvertOptions {
+    ConvertOptions { dry_run: true, ..ConvertOptions::default() }
+}
+
 #[test]
 fn test_convert_frontmatter() {
     let input = vec![
@@ -10,3 +14,2441 @@ fn test_convert_frontmatter() {
     let result = convert_frontmatter(&input);
     assert!(result.contains("order: 1"));
 }
+
+#[test]
+fn test_convert_admonitions_captures_full_type() {
+    assert!(convert_admonitions(":::note").contains("note"));
+    assert!(convert_admonitions(":::warning").contains("warning"));
+    assert!(convert_admonitions(":::danger").contains("important"));
+    assert!(!convert_admonitions(":::note").contains("{e}"));
+}
+
+#[test]
+fn test_convert_admonitions_callout_class() {
+    assert_eq!(convert_admonitions(":::note"), ":::: {.callout-note}");
+    assert_eq!(
+        convert_admonitions(":::note Heads up"),
+        ":::: {.callout-note}\n## Heads up"
+    );
+}
+
+#[test]
+fn test_convert_content_single_level_callout() {
+    let result = convert_content(":::note\nBody\n:::\n");
+    assert_eq!(result, ":::: {.callout-note}\nBody\n::::\n");
+}
+
+#[test]
+fn test_convert_content_nested_callout_fences() {
+    let input = ":::note\nOuter\n:::tip\nInner\n:::\nAfter\n:::\n";
+    let result = convert_content(input);
+    assert_eq!(
+        result,
+        ":::: {.callout-note}\nOuter\n::::: {.callout-tip}\nInner\n:::::\nAfter\n::::\n"
+    );
+}
+
+#[test]
+fn test_convert_content_docusaurus_wider_outer_fence() {
+    let input = "::::note Outer\n:::tip\nInner\n:::\nStill outer\n::::\n";
+    let result = convert_content(input);
+    assert_eq!(
+        result,
+        ":::: {.callout-note}\n## Outer\n::::: {.callout-tip}\nInner\n:::::\nStill outer\n::::\n"
+    );
+}
+
+#[test]
+fn test_convert_content_mismatched_close_keeps_output_balanced() {
+    // Closing with the wrong colon count warns but still closes the open callout
+    let result = convert_content("::::note\nBody\n:::\n");
+    assert_eq!(result, ":::: {.callout-note}\nBody\n::::\n");
+
+    // A stray close with nothing open is left untouched
+    let result = convert_content("Text\n:::\n");
+    assert_eq!(result, "Text\n:::\n");
+}
+
+#[test]
+fn test_convert_admonitions_block_multi_line_body() {
+    let block = ":::tip Try it\n\nFirst paragraph.\n\n- a list\n- of items\n\n:::";
+    assert_eq!(
+        convert_admonitions_block(block, CALLOUT_FENCE_WIDTH, CalloutTitleStyle::Attribute, &std::collections::BTreeMap::new()),
+        ":::: {.callout-tip title=\"Try it\"}\nFirst paragraph.\n\n- a list\n- of items\n::::"
+    );
+
+    // Through convert_content the blank lines around every body are trimmed, nested ones included
+    let input = ":::note Outer\n\nOuter body.\n\n:::tip\n\nInner body.\n\n:::\n\n:::\nAfter\n";
+    assert_eq!(
+        convert_content(input),
+        ":::: {.callout-note}\n## Outer\nOuter body.\n\n::::: {.callout-tip}\nInner body.\n:::::\n::::\nAfter\n"
+    );
+
+    // An unclosed block keeps its body as written
+    assert_eq!(convert_content(":::note\n\nBody\n"), ":::: {.callout-note}\n\nBody\n");
+}
+
+#[test]
+fn test_convert_details_html_to_collapse_callout() {
+    let input = "<details>\n<summary>Show the answer</summary>\n\nIt is **42**.\n\n<details open><summary>Why?</summary>\nNobody knows.\n</details>\n</details>\n";
+    let opts = ConvertOptions { convert_details: true, ..ConvertOptions::default() };
+    let result = convert_content_with_options(input, &opts);
+    assert_eq!(
+        result,
+        ":::: {.callout-note collapse=\"true\" title=\"Show the answer\"}\nIt is **42**.\n\n::::: {.callout-note collapse=\"false\" title=\"Why?\"}\nNobody knows.\n:::::\n::::\n"
+    );
+
+    // Off by default, and code blocks are never touched
+    assert_eq!(convert_content("<details>\nBody\n</details>\n"), "<details>\nBody\n</details>\n");
+    let code = "```html\n<details>\n</details>\n```\n";
+    assert_eq!(convert_details_html(code), code);
+}
+
+#[test]
+fn test_convert_admonition_title_attribute_style() {
+    let attr = |line| convert_admonition_fence(line, CALLOUT_FENCE_WIDTH, CalloutTitleStyle::Attribute);
+
+    assert_eq!(
+        attr(":::note My \"Quoted\" Title"),
+        ":::: {.callout-note title=\"My \\\"Quoted\\\" Title\"}"
+    );
+    assert_eq!(attr(":::tip [Array] access"), ":::: {.callout-tip title=\"[Array] access\"}");
+    assert_eq!(attr(":::warning Step 1: Backup"), ":::: {.callout-warning title=\"Step 1: Backup\"}");
+
+    // Untitled admonitions and the default heading style are unaffected
+    assert_eq!(attr(":::note"), ":::: {.callout-note}");
+    assert_eq!(
+        convert_content_with_title_style(":::note Heads up\n:::\n", CalloutTitleStyle::Heading),
+        convert_content(":::note Heads up\n:::\n")
+    );
+}
+
+#[test]
+fn test_convert_admonitions_bracketed_label() {
+    assert_eq!(
+        convert_admonitions(":::note[Remember]"),
+        ":::: {.callout-note}\n## Remember"
+    );
+    assert_eq!(
+        convert_admonitions(":::tip[Pro Tip]"),
+        ":::: {.callout-tip}\n## Pro Tip"
+    );
+    assert_eq!(
+        convert_admonition_fence(":::info[Custom Label]", CALLOUT_FENCE_WIDTH, CalloutTitleStyle::Attribute),
+        ":::: {.callout-note title=\"Custom Label\"}"
+    );
+}
+
+#[test]
+fn test_convert_links_rewrites_relative_doc_links() {
+    assert_eq!(convert_links("See [other](./other-page.md)."), "See [other](./other-page.qmd).");
+    assert_eq!(convert_links("[Intro](../guide/intro.md)"), "[Intro](../guide/intro.qmd)");
+    assert_eq!(convert_links("[Setup](./page.md#section)"), "[Setup](./page.qmd#section)");
+    assert_eq!(convert_links("[Comp](./widgets.mdx)"), "[Comp](./widgets.qmd)");
+    assert_eq!(convert_links("[ref]: ./page.md"), "[ref]: ./page.qmd");
+}
+
+#[test]
+fn test_convert_links_leaves_external_and_anchor_links() {
+    let line = "[Site](https://example.com/readme.md) [Top](#top) ![img](./img/a.png)";
+    assert_eq!(convert_links(line), line);
+    assert_eq!(
+        convert_content("Read [this](./next.md) next\n"),
+        "Read [this](./next.qmd) next\n"
+    );
+}
+
+#[test]
+fn test_is_markdown_file_mdx_opt_out() {
+    use std::path::Path;
+
+    assert!(is_markdown_file(Path::new("docs/intro.md"), false));
+    assert!(is_markdown_file(Path::new("docs/widgets.mdx"), true));
+    assert!(!is_markdown_file(Path::new("docs/widgets.mdx"), false));
+    assert!(!is_markdown_file(Path::new("docs/img/logo.png"), true));
+}
+
+#[test]
+fn test_process_files_mdx_writes_qmd() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let nested = source.path().join("guide");
+    std::fs::create_dir_all(&nested).unwrap();
+    let source_file = nested.join("widgets.mdx");
+    std::fs::write(&source_file, ":::tip\nUse MDX\n:::\n").unwrap();
+
+    process_files(&source_file, source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+
+    let output = dest.path().join("guide").join("widgets.qmd");
+    assert!(output.exists());
+    assert!(std::fs::read_to_string(output).unwrap().contains(".callout-tip"));
+}
+
+#[test]
+fn test_strip_mdx_statements_removes_imports() {
+    let input = "\
+import Tabs from '@theme/Tabs';
+import TabItem from '@theme/TabItem';
+import {
+  Highlight,
+  Badge,
+} from '@site/src/components';
+export const toc = [];
+
+# Title
+
+Import the module before use.
+import is also a word in prose.
+
+```js
+import React from 'react';
+```
+";
+    let (output, removed) = strip_mdx_statements(input);
+
+    assert_eq!(removed.len(), 4);
+    assert!(output.starts_with("\n# Title"));
+    assert!(output.contains("Import the module before use."));
+    assert!(output.contains("import is also a word in prose."));
+    assert!(output.contains("import React from 'react';"));
+    assert!(!output.contains("@theme/Tabs"));
+}
+
+#[test]
+fn test_strip_jsx_comments_single_and_multi_line() {
+    let input = "\
+# Title
+{/* TODO: rewrite this section */}
+Keep {/* inline note */} this sentence.
+
+{/*
+  Multi-line comment
+  with ```code``` in it
+*/}
+<!-- An HTML comment stays -->
+## Heading {#custom-id}
+
+Use `{/* comment */}` in MDX files.
+
+```jsx
+{/* Comments inside code are examples */}
+```
+";
+    assert_eq!(
+        convert_content(input),
+        "\
+# Title
+Keep  this sentence.
+
+<!-- An HTML comment stays -->
+## Heading {#custom-id}
+
+Use `{/* comment */}` in MDX files.
+
+```jsx
+{/* Comments inside code are examples */}
+```
+"
+    );
+}
+
+#[test]
+fn test_unclosed_jsx_comment_keeps_the_rest_of_the_file() {
+    let input = "import X from './x';\n\nKeep {/* closed */} this.\n\nStart {/* never closed\nstill here\n\n## Heading\n";
+    assert_eq!(strip_jsx_comments(input), "import X from './x';\n\nKeep  this.\n\nStart {/* never closed\nstill here\n\n## Heading\n");
+
+    let (converted, warnings) = convert_content_with_report(input, &ConvertOptions::default());
+    assert!(converted.ends_with("Start {/* never closed\nstill here\n\n## Heading\n"));
+    let unclosed = warnings.iter().find(|w| w.kind == WarningKind::UnclosedComment).unwrap();
+    assert_eq!(unclosed.line, Some(5));
+}
+
+#[test]
+fn test_strip_jsx_comments_keeps_double_backtick_code() {
+    assert_eq!(strip_jsx_comments("``{/* kept? */}`` text\n"), "``{/* kept? */}`` text\n");
+    assert_eq!(strip_jsx_comments("``a ` {/* kept */}`` {/* gone */}b\n"), "``a ` {/* kept */}`` b\n");
+}
+
+#[test]
+fn test_warning_lines_follow_the_source_after_rewritten_lines() {
+    let lines = |input: &str| -> Vec<(WarningKind, Option<usize>)> {
+        convert_content_with_report(input, &ConvertOptions::default()).1.iter().map(|w| (w.kind, w.line)).collect()
+    };
+
+    let input = "import A from 'a';\nimport B from 'b';\n\n:::bogus\ntext\n:::\n<Foo />\n";
+    assert_eq!(lines(input), vec![(WarningKind::UnknownAdmonition, Some(4)), (WarningKind::ResidualComponent, Some(7))]);
+
+    let input = "{/* a\ncomment */}\n<Tabs>\n<TabItem value=\"a\">\n:::bogus\nx\n:::\n</TabItem>\n</Tabs>\n<Foo />\n";
+    assert_eq!(lines(input), vec![(WarningKind::UnknownAdmonition, Some(5)), (WarningKind::ResidualComponent, Some(10))]);
+}
+
+#[test]
+fn test_convert_tabs_two_tab_example() {
+    let input = "\
+import Tabs from '@theme/Tabs';
+import TabItem from '@theme/TabItem';
+
+<Tabs defaultValue=\"apple\" values={[
+  {label: 'Apple', value: 'apple'},
+  {label: 'Orange', value: 'orange'},
+]}>
+  <TabItem value=\"apple\" label=\"Apple\" default>
+    This is an apple 🍎
+  </TabItem>
+  <TabItem value=\"orange\">
+    This is an orange 🍊
+  </TabItem>
+</Tabs>
+";
+    let expected = "
+::: {.panel-tabset}
+
+## Apple
+
+This is an apple 🍎
+
+## orange
+
+This is an orange 🍊
+
+:::
+";
+    assert_eq!(convert_content(input), expected);
+}
+
+#[test]
+fn test_process_files_dry_run_writes_nothing() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("img")).unwrap();
+    std::fs::write(source.path().join("img").join("a.png"), b"png").unwrap();
+    let source_file = source.path().join("intro.md");
+    std::fs::write(&source_file, "# Intro\n").unwrap();
+
+    process_files(&source_file, source.path(), dest.path(), &dry_run()).unwrap();
+
+    assert_eq!(std::fs::read_dir(dest.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn test_process_files_returns_report() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("img")).unwrap();
+    std::fs::write(source.path().join("img").join("a.png"), b"png").unwrap();
+    let source_file = source.path().join("intro.md");
+    let input = ":::note\nHi\n:::\n";
+    std::fs::write(&source_file, input).unwrap();
+
+    let report = process_files(&source_file, source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+
+    assert_eq!(report.source_path, source_file);
+    assert_eq!(report.dest_path, dest.path().join("intro.qmd"));
+    assert_eq!(report.bytes_read, input.len());
+    assert_eq!(report.bytes_written, ":::: {.callout-note}\nHi\n::::\n".len());
+    assert!(report.img_copied);
+
+    let dry = process_files(&source_file, source.path(), dest.path(), &dry_run()).unwrap();
+    assert_eq!(dry.bytes_written, 0);
+}
+
+#[test]
+fn test_cli_reports_summary_and_fails_on_error() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join("good.md"), "# Good\n").unwrap();
+    // Invalid UTF-8 cannot be read as a String
+    std::fs::write(source.path().join("bad.md"), [0xff, 0xfe, 0xfd]).unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_doc2quarto"))
+        .arg("--source")
+        .arg(source.path())
+        .arg("--dest")
+        .arg(dest.path())
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("1 converted, 0 skipped, 1 failed"));
+    assert_eq!(output.status.code(), Some(1));
+    assert!(dest.path().join("good.qmd").exists());
+}
+
+#[test]
+fn test_convert_frontmatter_sidebar_label_without_title() {
+    let input = vec!["sidebar_label: \"Quick Start\"", "sidebar_position: 2"];
+
+    let result = convert_frontmatter(&input);
+    assert!(result.contains("title: Quick Start"));
+    assert!(!result.contains("sidebar_label"));
+}
+
+#[test]
+fn test_convert_frontmatter_sidebar_label_with_title() {
+    let input = vec!["title: \"Getting Started\"", "sidebar_label: \"Start\""];
+
+    let result = convert_frontmatter(&input);
+    assert_eq!(result, "title: Getting Started\n");
+}
+
+#[test]
+fn test_convert_frontmatter_preserves_yaml_structure() {
+    let input = vec![
+        "title: \"Foo: Bar\"",
+        "sidebar_position: 3",
+        "tags:",
+        "  - rust",
+        "  - docs",
+        "author:",
+        "  name: Jane",
+        "  url: https://example.com",
+    ];
+
+    let result = convert_frontmatter(&input);
+    let parsed: serde_yaml::Mapping = serde_yaml::from_str(&result).unwrap();
+
+    assert_eq!(parsed["title"], "Foo: Bar");
+    assert_eq!(parsed["order"], 3);
+    assert_eq!(parsed["categories"][1], "docs");
+    assert_eq!(parsed["author"]["url"], "https://example.com");
+    assert!(!parsed.contains_key("sidebar_position"));
+}
+
+#[test]
+fn test_convert_frontmatter_tags_to_categories() {
+    let flow = convert_frontmatter(&["tags: [Quantum, ML]"]);
+    let block = convert_frontmatter(&["tags:", "  - Quantum", "  - ML"]);
+
+    for result in [flow, block] {
+        let parsed: serde_yaml::Mapping = serde_yaml::from_str(&result).unwrap();
+        assert_eq!(parsed["categories"], serde_yaml::from_str::<serde_yaml::Value>("[Quantum, ML]").unwrap());
+        assert!(!parsed.contains_key("tags"));
+    }
+
+    // Related fields are left alone
+    let result = convert_frontmatter(&["keywords: [a, b]"]);
+    assert!(result.contains("keywords"));
+}
+
+#[test]
+fn test_convert_frontmatter_slug_to_aliases() {
+    let root = convert_frontmatter(&["slug: /custom/path/"]);
+    let parsed: serde_yaml::Mapping = serde_yaml::from_str(&root).unwrap();
+    assert_eq!(parsed["aliases"][0], "/custom/path");
+    assert!(!parsed.contains_key("slug"));
+
+    let relative = convert_frontmatter(&["slug: QuantumML", "aliases: [old-quantum.html]"]);
+    let parsed: serde_yaml::Mapping = serde_yaml::from_str(&relative).unwrap();
+    assert_eq!(parsed["aliases"][0], "old-quantum.html");
+    assert_eq!(parsed["aliases"][1], "QuantumML");
+}
+
+#[test]
+fn test_convert_content_toml_frontmatter() {
+    let input = "+++\ntitle = \"TOML Page\"\nsidebar_position = 4\n+++\n# Body\n";
+    let result = convert_content(input);
+
+    assert!(result.starts_with("---\n"));
+    assert!(result.contains("title: TOML Page\n"));
+    assert!(result.contains("order: 4\n"));
+    assert!(result.ends_with("# Body\n"));
+    assert!(!result.contains("+++"));
+}
+
+#[test]
+fn test_convert_content_frontmatter_delimiters_with_trailing_whitespace() {
+    let expected = "---\ntitle: Page\norder: 2\n---\nBody with trailing spaces  \n";
+    let input = "---   \ntitle: Page\nsidebar_position: 2\n---\t\nBody with trailing spaces  \n";
+    assert_eq!(convert_content(input), expected);
+
+    let input = "---\r\ntitle: Page\nsidebar_position: 2\n--- \r\nBody with trailing spaces  \n";
+    assert_eq!(convert_content(input), expected);
+
+    // A TOML block may close with trailing whitespace too
+    let result = convert_content("+++\ntitle = \"Page\"\n+++  \nBody\n");
+    assert_eq!(result, "---\ntitle: Page\n---\nBody\n");
+}
+
+#[test]
+fn test_convert_content_thematic_breaks_without_frontmatter() {
+    let input = "# Title\n\nIntro\n\n---\n\nMiddle\n\n---\n\nEnd\n";
+    assert_eq!(convert_content(input), input);
+}
+
+#[test]
+fn test_convert_content_thematic_break_after_frontmatter() {
+    let input = "---\ntitle: Page\n---\nIntro\n\n---\n\nMore text\n";
+    let result = convert_content(input);
+
+    assert!(result.contains("title: Page\n"));
+    assert!(result.contains("Intro\n\n---\n\nMore text\n"));
+}
+
+#[test]
+fn test_convert_content_leaves_code_blocks_verbatim() {
+    let input = "\
+Write admonitions like this:
+
+```markdown title=\"example.md\"
+:::note
+See [intro](./intro.md)
+:::
+```
+
+  ~~~~
+  ```
+  :::tip
+  ```
+  ~~~~
+
+:::note
+Real callout
+:::
+";
+    let result = convert_content(input);
+
+    assert!(result.contains("```{.markdown filename=\"example.md\"}\n:::note\nSee [intro](./intro.md)\n:::\n```\n"));
+    assert!(result.contains("  ```\n  :::tip\n  ```\n"));
+    assert!(result.contains(":::: {.callout-note}\nReal callout\n::::\n"));
+}
+
+#[test]
+fn test_convert_tree_returns_reports() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("guide")).unwrap();
+    std::fs::write(source.path().join("intro.md"), "# Intro\n").unwrap();
+    std::fs::write(source.path().join("guide").join("tabs.mdx"), "# Tabs\n").unwrap();
+    std::fs::write(source.path().join("notes.txt"), "not markdown").unwrap();
+
+    let reports = convert_tree(source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+
+    assert_eq!(reports.len(), 2);
+    assert!(reports.iter().all(|r| r.is_success()));
+    assert_eq!(reports[0].dest_path, dest.path().join("guide").join("tabs.qmd"));
+    assert_eq!(reports[1].dest_path, dest.path().join("intro.qmd"));
+    assert!(dest.path().join("intro.qmd").exists());
+
+    let md_only = ConvertOptions { include_mdx: false, ..ConvertOptions::default() };
+    let reports = convert_tree(source.path(), dest.path(), &md_only).unwrap();
+    assert_eq!(reports.len(), 1);
+}
+
+#[test]
+fn test_convert_content_with_options_disables_passes() {
+    let input = "<Tabs>\n<TabItem value=\"a\">\nA\n</TabItem>\n</Tabs>\nSee [next](./next.md)\n:::note Title\n:::\n";
+    let opts = ConvertOptions {
+        convert_links: false,
+        convert_tabs: false,
+        callout_title_style: CalloutTitleStyle::Attribute,
+        ..ConvertOptions::default()
+    };
+
+    let result = convert_content_with_options(input, &opts);
+
+    assert!(result.contains("<Tabs>\n<TabItem value=\"a\">"));
+    assert!(result.contains("[next](./next.md)"));
+    assert!(result.contains(":::: {.callout-note title=\"Title\"}"));
+    assert_eq!(convert_content_with_options(input, &ConvertOptions::default()), convert_content(input));
+}
+
+#[test]
+fn test_process_files_without_image_copy() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("img")).unwrap();
+    std::fs::write(source.path().join("img").join("a.png"), b"png").unwrap();
+    let source_file = source.path().join("intro.md");
+    std::fs::write(&source_file, "[Next](./next.md)\n").unwrap();
+
+    let opts = ConvertOptions { copy_images: false, convert_links: false, ..ConvertOptions::default() };
+    let report = process_files(&source_file, source.path(), dest.path(), &opts).unwrap();
+
+    assert!(!report.img_copied);
+    assert!(!dest.path().join("img").exists());
+    assert_eq!(std::fs::read_to_string(dest.path().join("intro.qmd")).unwrap(), "[Next](./next.md)\n");
+}
+
+#[test]
+fn test_process_files_copies_images_folder() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("images")).unwrap();
+    std::fs::write(source.path().join("images").join("diagram.png"), b"png").unwrap();
+    let source_file = source.path().join("intro.md");
+    std::fs::write(&source_file, "![d](./images/diagram.png)\n").unwrap();
+
+    let report = process_files(&source_file, source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+    assert!(report.img_copied);
+    assert!(dest.path().join("images").join("diagram.png").exists());
+
+    // Only configured names are copied
+    let other = tempfile::tempdir().unwrap();
+    let opts = ConvertOptions { image_folders: vec!["img".to_string()], ..ConvertOptions::default() };
+    let report = process_files(&source_file, source.path(), other.path(), &opts).unwrap();
+    assert!(!report.img_copied);
+    assert!(!other.path().join("images").exists());
+    assert!(!copy_img_folder(&source_file, &other.path().join("intro.qmd")).unwrap());
+}
+
+#[test]
+fn test_copy_img_folder_copies_nested_subfolders() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let deep = source.path().join("img").join("diagrams").join("v2");
+    std::fs::create_dir_all(&deep).unwrap();
+    std::fs::write(source.path().join("img").join("top.png"), b"top").unwrap();
+    std::fs::write(deep.join("flow.png"), b"flow").unwrap();
+    let source_file = source.path().join("intro.md");
+    std::fs::write(&source_file, "# Intro\n").unwrap();
+
+    assert!(copy_img_folder(&source_file, &dest.path().join("intro.qmd")).unwrap());
+
+    assert!(dest.path().join("img").join("top.png").exists());
+    let nested = dest.path().join("img").join("diagrams").join("v2").join("flow.png");
+    assert_eq!(std::fs::read(nested).unwrap(), b"flow");
+
+    // Copying a tree onto itself leaves the files intact
+    assert!(copy_img_folder(&source_file, &source_file).unwrap());
+    assert_eq!(std::fs::read(deep.join("flow.png")).unwrap(), b"flow");
+}
+
+#[test]
+fn test_rewrite_image_references() {
+    let folders = DEFAULT_IMAGE_FOLDERS;
+
+    assert_eq!(rewrite_image_references("![a](./img/foo.png)", folders), "![a](img/foo.png)");
+    assert_eq!(rewrite_image_references("![a](../img/foo.png \"Foo\")", folders), "![a](img/foo.png \"Foo\")");
+    assert_eq!(
+        rewrite_image_references("<img src=\"../../assets/x/y.svg\" width=\"50\"/>", folders),
+        "<img src=\"assets/x/y.svg\" width=\"50\"/>"
+    );
+    assert_eq!(rewrite_image_references("<img alt='a' src='./images/b.png'>", folders), "<img alt='a' src='images/b.png'>");
+
+    // URLs, root-relative paths and files outside image folders are left alone
+    let line = "![u](https://example.com/img/a.png) ![r](/img/a.png) ![o](./other/a.png) ![k](img/a.png)";
+    assert_eq!(rewrite_image_references(line, folders), line);
+}
+
+#[test]
+fn test_process_files_relocates_parent_images() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("img")).unwrap();
+    std::fs::create_dir_all(source.path().join("guide")).unwrap();
+    std::fs::write(source.path().join("img").join("shared.png"), b"shared").unwrap();
+    let source_file = source.path().join("guide").join("setup.md");
+    std::fs::write(&source_file, "![s](../img/shared.png)\n").unwrap();
+
+    let report = process_files(&source_file, source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+
+    assert!(report.img_copied);
+    let output = std::fs::read_to_string(dest.path().join("guide").join("setup.qmd")).unwrap();
+    assert_eq!(output, "![s](img/shared.png)\n");
+    assert_eq!(std::fs::read(dest.path().join("guide").join("img").join("shared.png")).unwrap(), b"shared");
+}
+
+#[test]
+fn test_process_files_invalid_utf8_policy() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let source_file = source.path().join("latin1.md");
+    std::fs::write(&source_file, b"# Caf\xe9\n").unwrap();
+
+    let err = process_files(&source_file, source.path(), dest.path(), &ConvertOptions::default()).unwrap_err();
+    assert!(err.to_string().contains("not valid UTF-8"));
+    assert!(!dest.path().join("latin1.qmd").exists());
+
+    let lossy = ConvertOptions { invalid_utf8: InvalidUtf8::Lossy, ..ConvertOptions::default() };
+    process_files(&source_file, source.path(), dest.path(), &lossy).unwrap();
+    assert_eq!(std::fs::read_to_string(dest.path().join("latin1.qmd")).unwrap(), "# Caf\u{fffd}\n");
+}
+
+#[test]
+fn test_config_maps_custom_admonition_types() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("doc2quarto.toml");
+    std::fs::write(&path, "[admonitions]\nhint = \"tip\"\nSecret = \"important\"\n").unwrap();
+
+    let config = Config::load(&path).unwrap();
+    assert_eq!(config.admonitions["hint"], "tip");
+    assert_eq!(config, Config::from_yaml_str("admonitions:\n  hint: tip\n  secret: important\n").unwrap());
+
+    let opts = ConvertOptions { admonition_types: config.admonitions, ..ConvertOptions::default() };
+    let result = convert_content_with_options(":::hint Try this\n:::\n:::custom\n:::\n:::danger\n:::\n", &opts);
+    assert_eq!(
+        result,
+        ":::: {.callout-tip}\n## Try this\n::::\n:::: {.callout-custom}\n::::\n:::: {.callout-important}\n::::\n"
+    );
+
+    assert!(matches!(Config::from_yaml_str("admonitions: [hint]"), Err(ConfigError::InvalidSetting(_))));
+
+    // File errors carry the path and the parse error
+    std::fs::write(&path, "[admonitions\n").unwrap();
+    let error = Config::load(&path).unwrap_err();
+    assert!(matches!(&error, ConfigError::InvalidFile { path: p, source } if *p == path && matches!(**source, ConfigError::Toml(_))));
+    assert!(matches!(Config::load(&dir.path().join("missing.yml")), Err(ConfigError::ReadFailed { .. })));
+}
+
+#[test]
+fn test_convert_frontmatter_merges_metadata() {
+    let metadata: serde_yaml::Mapping = serde_yaml::from_str("format: html\ntitle: Default\ntoc: true").unwrap();
+
+    let result = convert_frontmatter_with_metadata(&["title: Page", "sidebar_position: 2"], &metadata);
+    let parsed: serde_yaml::Mapping = serde_yaml::from_str(&result).unwrap();
+    assert_eq!(parsed["title"], "Page");
+    assert_eq!(parsed["order"], 2);
+    assert_eq!(parsed["format"], "html");
+    assert_eq!(parsed["toc"], true);
+
+    // Files without frontmatter get a block containing only the metadata
+    let opts = ConvertOptions { metadata, ..ConvertOptions::default() };
+    assert_eq!(
+        convert_content_with_options("# Body\n", &opts),
+        "---\nformat: html\ntitle: Default\ntoc: true\n---\n# Body\n"
+    );
+    let toml = convert_content_with_options("+++\ntoc = false\n+++\n", &opts);
+    assert!(toml.contains("toc: false\n"));
+}
+
+#[test]
+fn test_find_jsx_components_reports_residual_tags() {
+    let content = "# Title\n\n<MyComponent prop=\"x\"/>\n<div>html</div>\nUse `<Inline/>` here\n```jsx\n<Code/>\n```\n<Foo.Bar>\n";
+    let found = find_jsx_components(content);
+
+    assert_eq!(
+        found,
+        vec![
+            ResidualComponent { line: 3, name: "MyComponent".to_string() },
+            ResidualComponent { line: 9, name: "Foo.Bar".to_string() },
+        ]
+    );
+}
+
+#[test]
+fn test_cli_strict_fails_on_residual_components() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join("widget.mdx"), "# Widget\n\n<Chart data=\"sales.csv\" />\n").unwrap();
+
+    let run = |strict: bool| {
+        let mut command = std::process::Command::new(env!("CARGO_BIN_EXE_doc2quarto"));
+        command.arg("--source").arg(source.path()).arg("--dest").arg(dest.path());
+        if strict {
+            command.arg("--strict");
+        }
+        command.output().unwrap()
+    };
+
+    let lenient = run(false);
+    assert_eq!(lenient.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&lenient.stdout).contains("<Chart>"));
+    assert_eq!(run(true).status.code(), Some(1));
+}
+
+#[test]
+fn test_cli_converts_single_file() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let source_file = source.path().join("intro.md");
+    std::fs::write(&source_file, ":::tip\nOne file\n:::\n").unwrap();
+    let output_file = dest.path().join("out").join("renamed.qmd");
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_doc2quarto"))
+        .arg("--source")
+        .arg(&source_file)
+        .arg("--dest")
+        .arg(&output_file)
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(std::fs::read_to_string(&output_file).unwrap(), ":::: {.callout-tip}\nOne file\n::::\n");
+
+    // A destination without an extension is a directory
+    let report = convert_file(&source_file, &dest.path().join("docs"), &ConvertOptions::default()).unwrap();
+    assert_eq!(report.dest_path, dest.path().join("docs").join("intro.qmd"));
+    assert!(report.dest_path.exists());
+}
+
+#[test]
+fn test_cli_list_prints_mappings_without_converting() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("guide")).unwrap();
+    std::fs::create_dir_all(source.path().join("drafts")).unwrap();
+    std::fs::write(source.path().join("intro.md"), "# Intro\n").unwrap();
+    std::fs::write(source.path().join("guide").join("setup.mdx"), "# Setup\n").unwrap();
+    std::fs::write(source.path().join("drafts").join("wip.md"), "# WIP\n").unwrap();
+    let out = dest.path().join("out");
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_doc2quarto"))
+        .arg("--source")
+        .arg(source.path())
+        .arg("--dest")
+        .arg(&out)
+        .args(["--list", "--exclude", "drafts/**"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    let expected = format!(
+        "{} → {}\n{} → {}\n",
+        source.path().join("guide").join("setup.mdx").display(),
+        out.join("guide").join("setup.qmd").display(),
+        source.path().join("intro.md").display(),
+        out.join("intro.qmd").display(),
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), expected);
+    assert!(!out.exists());
+}
+
+#[test]
+fn test_cli_report_file_csv_and_json() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join("a.md"), "# A\n").unwrap();
+    std::fs::write(source.path().join("b.md"), ":::note\nNever closed\n").unwrap();
+    let run = |report: &std::path::Path| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_doc2quarto"))
+            .arg("--source")
+            .arg(source.path())
+            .arg("--dest")
+            .arg(dest.path())
+            .arg("--report-file")
+            .arg(report)
+            .arg("--quiet")
+            .output()
+            .unwrap()
+    };
+
+    let csv = dest.path().join("report.csv");
+    assert_eq!(run(&csv).status.code(), Some(0));
+    let expected = format!(
+        "source,dest,status,bytes_read,bytes_written,error,warnings\n{},{},converted,4,4,,\n{},{},converted,21,34,,unclosed_admonition: 1 admonition(s) left open at end of file\n",
+        source.path().join("a.md").display(),
+        dest.path().join("a.qmd").display(),
+        source.path().join("b.md").display(),
+        dest.path().join("b.qmd").display(),
+    );
+    assert_eq!(std::fs::read_to_string(&csv).unwrap(), expected);
+
+    let json = dest.path().join("report.json");
+    assert_eq!(run(&json).status.code(), Some(0));
+    let summary: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&json).unwrap()).unwrap();
+    assert_eq!(summary["converted"], 2);
+    assert_eq!(summary["files"][1]["warnings"][0]["kind"], "unclosed_admonition");
+}
+
+#[test]
+fn test_process_files_existing_output_policies() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let source_file = source.path().join("intro.md");
+    std::fs::write(&source_file, "# New\n").unwrap();
+    let dest_file = dest.path().join("intro.qmd");
+    let policy = |existing_output| ConvertOptions { existing_output, ..ConvertOptions::default() };
+
+    std::fs::write(&dest_file, "# Edited by hand\n").unwrap();
+    let report = process_files(&source_file, source.path(), dest.path(), &policy(ExistingOutput::Skip)).unwrap();
+    assert!(report.skipped);
+    assert_eq!(std::fs::read_to_string(&dest_file).unwrap(), "# Edited by hand\n");
+
+    let report = process_files(&source_file, source.path(), dest.path(), &policy(ExistingOutput::Backup)).unwrap();
+    assert!(!report.skipped);
+    assert_eq!(std::fs::read_to_string(&dest_file).unwrap(), "# New\n");
+    assert_eq!(std::fs::read_to_string(dest.path().join("intro.qmd.bak")).unwrap(), "# Edited by hand\n");
+
+    std::fs::write(&dest_file, "# Edited again\n").unwrap();
+    process_files(&source_file, source.path(), dest.path(), &policy(ExistingOutput::Overwrite)).unwrap();
+    assert_eq!(std::fs::read_to_string(&dest_file).unwrap(), "# New\n");
+    assert_eq!(std::fs::read_to_string(dest.path().join("intro.qmd.bak")).unwrap(), "# Edited by hand\n");
+}
+
+#[test]
+fn test_process_files_preserves_mtime() {
+    use std::time::{Duration, SystemTime};
+
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let source_file = source.path().join("intro.md");
+    std::fs::write(&source_file, "# Intro\n").unwrap();
+    let source_mtime = SystemTime::now() - Duration::from_secs(3600);
+    std::fs::File::options().write(true).open(&source_file).unwrap().set_modified(source_mtime).unwrap();
+
+    let opts = ConvertOptions { preserve_mtime: true, ..ConvertOptions::default() };
+    let report = process_files(&source_file, source.path(), dest.path(), &opts).unwrap();
+
+    let dest_mtime = std::fs::metadata(&report.dest_path).unwrap().modified().unwrap();
+    let diff = dest_mtime.duration_since(source_mtime).unwrap_or_else(|e| e.duration());
+    assert!(diff < Duration::from_secs(1));
+}
+
+#[test]
+fn test_convert_tree_incremental_skips_unchanged() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("guide")).unwrap();
+    std::fs::write(source.path().join("intro.md"), "# Intro\n").unwrap();
+    std::fs::write(source.path().join("guide").join("setup.md"), "# Setup\n").unwrap();
+    let opts = ConvertOptions { incremental: true, ..ConvertOptions::default() };
+
+    let first = convert_tree(source.path(), dest.path(), &opts).unwrap();
+    assert!(first.iter().all(|r| r.is_success() && !r.skipped));
+    assert!(dest.path().join(MANIFEST_FILE).exists());
+
+    let second = convert_tree(source.path(), dest.path(), &opts).unwrap();
+    assert_eq!(second.len(), 2);
+    assert!(second.iter().all(|r| r.skipped && r.unchanged));
+
+    // Only the edited file is converted again
+    std::fs::write(source.path().join("intro.md"), "# Intro, edited\n").unwrap();
+    let third = convert_tree(source.path(), dest.path(), &opts).unwrap();
+    assert_eq!(third.iter().filter(|r| r.skipped).count(), 1);
+    assert_eq!(std::fs::read_to_string(dest.path().join("intro.qmd")).unwrap(), "# Intro, edited\n");
+
+    // Names JSON has to escape still give a valid manifest
+    #[cfg(unix)]
+    {
+        std::fs::write(source.path().join("say \"hi\" \\ bye.md"), "# Quoted\n").unwrap();
+        convert_tree(source.path(), dest.path(), &opts).unwrap();
+        let manifest: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(dest.path().join(MANIFEST_FILE)).unwrap()).unwrap();
+        assert!(manifest["files"]["say \"hi\" \\ bye.md"].is_string());
+        let again = convert_tree(source.path(), dest.path(), &opts).unwrap();
+        assert!(again.iter().all(|r| r.unchanged));
+    }
+}
+
+#[test]
+fn test_cli_counts_unchanged_files_apart_from_skipped() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join("intro.md"), "# Intro\n").unwrap();
+    std::fs::write(source.path().join("setup.md"), "# Setup\n").unwrap();
+    let run = |args: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_doc2quarto"))
+            .arg("--source")
+            .arg(source.path())
+            .arg("--dest")
+            .arg(dest.path())
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    run(&["--incremental"]);
+    // A file converted outside the manifest exists but is not known to be up to date
+    std::fs::write(source.path().join("setup.md"), "# Setup, edited\n").unwrap();
+    let output = run(&["--incremental", "--no-clobber"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("0 converted, 1 skipped, 0 failed"), "{}", stdout);
+    assert!(stdout.contains("1 file(s) unchanged since the last run"), "{}", stdout);
+
+    let summary: serde_json::Value = serde_json::from_slice(&run(&["--incremental", "--no-clobber", "--json"]).stdout).unwrap();
+    assert_eq!((summary["converted"].as_u64(), summary["skipped"].as_u64(), summary["unchanged"].as_u64()), (Some(0), Some(1), Some(1)));
+    assert_eq!(summary["files"][0]["unchanged"], true);
+    assert_eq!(summary["files"][1]["unchanged"], false);
+}
+
+#[test]
+fn test_convert_content_truncate_marker() {
+    let post = "---\ntitle: Launch\n---\n# Launch\n\nWe shipped the new release.\nIt is fast.\n\n<!-- truncate -->\n\nFull details here.\n";
+
+    assert_eq!(convert_content(post), convert_content_with_options(post, &ConvertOptions::default()));
+    assert!(convert_content(post).contains("<!-- truncate -->"));
+
+    let remove = ConvertOptions { truncate_marker: TruncateMarker::Remove, ..ConvertOptions::default() };
+    let removed = convert_content_with_options(post, &remove);
+    assert!(!removed.contains("truncate"));
+    assert!(!removed.contains("description"));
+
+    let describe = ConvertOptions { truncate_marker: TruncateMarker::Description, ..ConvertOptions::default() };
+    let described = convert_content_with_options(post, &describe);
+    assert!(described.starts_with("---\ntitle: Launch\ndescription: We shipped the new release. It is fast.\n---\n"));
+    assert!(!described.contains("<!--"));
+    assert!(described.ends_with("Full details here.\n"));
+
+    // An explicit description wins over the summary
+    let own = convert_content_with_options("---\ndescription: Mine\n---\nText\n<!--truncate-->\n", &describe);
+    assert!(own.contains("description: Mine\n"));
+}
+
+#[test]
+fn test_convert_frontmatter_doc_id() {
+    let lines = ["id: getting-started", "title: Start"];
+    assert_eq!(convert_frontmatter(&lines), "title: Start\n");
+
+    let metadata = serde_yaml::Mapping::new();
+    let alias = convert_frontmatter_with_options(&lines, &FrontmatterOptions { metadata: &metadata, doc_id: DocId::Alias, site_root: "", dropped_keys: DEFAULT_DROPPED_KEYS, authors: &serde_yaml::Mapping::new() });
+    let parsed: serde_yaml::Mapping = serde_yaml::from_str(&alias).unwrap();
+    assert_eq!(parsed["aliases"][0], "getting-started");
+    assert!(!parsed.contains_key("id"));
+
+    let keep = convert_frontmatter_with_options(&lines, &FrontmatterOptions { metadata: &metadata, doc_id: DocId::Keep, site_root: "", dropped_keys: DEFAULT_DROPPED_KEYS, authors: &serde_yaml::Mapping::new() });
+    assert!(keep.contains("id: getting-started"));
+}
+
+#[test]
+fn test_cli_verbose_and_quiet_logging() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join("intro.md"), "# Intro\n").unwrap();
+
+    let run = |flag: Option<&str>| {
+        let mut command = std::process::Command::new(env!("CARGO_BIN_EXE_doc2quarto"));
+        command.arg("--source").arg(source.path()).arg("--dest").arg(dest.path());
+        if let Some(flag) = flag {
+            command.arg(flag);
+        }
+        let output = command.output().unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        (String::from_utf8_lossy(&output.stdout).into_owned(), String::from_utf8_lossy(&output.stderr).into_owned())
+    };
+
+    let (_, verbose) = run(Some("--verbose"));
+    assert!(verbose.contains("Read 8 bytes"));
+    assert!(verbose.contains("Written to"));
+
+    let (_, default) = run(None);
+    assert!(!default.contains("Read 8 bytes"));
+
+    let (stdout, quiet) = run(Some("--quiet"));
+    assert!(quiet.is_empty());
+    assert!(!stdout.contains("Found 1 markdown files"));
+    assert!(stdout.contains("1 converted"));
+}
+
+#[test]
+fn test_convert_heading_attributes() {
+    assert_eq!(convert_heading_attributes("## Title {#my-id}"), "## Title {#my-id}");
+    assert_eq!(convert_heading_attributes("## Title   {  #my-id  }"), "## Title {#my-id}");
+    assert_eq!(convert_heading_attributes("### API {.unnumbered #api/v2 data-level=2}"), "### API {#api-v2 .unnumbered data-level=2}");
+    assert_eq!(convert_heading_attributes("# Café {#café-menü!}"), "# Café {#café-menü}");
+    assert_eq!(convert_heading_attributes("## Only {.cls}"), "## Only {.cls}");
+
+    // Not a heading, or no attribute block
+    assert_eq!(convert_heading_attributes("Text {#not-heading}"), "Text {#not-heading}");
+    assert_eq!(convert_heading_attributes("## Plain"), "## Plain");
+
+    assert_eq!(convert_content("## Setup {#step_1?}\n"), "## Setup {#step_1}\n");
+}
+
+#[test]
+fn test_resolve_site_aliases() {
+    let line = "![Logo](@site/static/img/logo.png) <img src=\"@site/static/img/a.svg\"/> [Guide](@site/docs/guide.md)";
+    assert_eq!(
+        resolve_site_aliases(line, Some("/")),
+        "![Logo](/static/img/logo.png) <img src=\"/static/img/a.svg\"/> [Guide](/docs/guide.md)"
+    );
+    assert_eq!(
+        resolve_site_aliases("[ref]: @site/static/file.pdf", Some("https://example.com/")),
+        "[ref]: https://example.com/static/file.pdf"
+    );
+
+    // Unresolvable aliases are left alone
+    assert_eq!(resolve_site_aliases(line, None), line);
+    let module = "![x](@docusaurus/useBaseUrl)";
+    assert_eq!(resolve_site_aliases(module, Some("/")), module);
+
+    let opts = ConvertOptions { site_base: Some("/".to_string()), ..ConvertOptions::default() };
+    assert_eq!(
+        convert_content_with_options("![Logo](@site/static/img/logo.png)\n", &opts),
+        "![Logo](/static/img/logo.png)\n"
+    );
+
+    // Unresolved aliases are reported with their line, inline code is left alone
+    let code = "Write `[x](@site/docs/a.md)` for site links.";
+    assert_eq!(resolve_site_aliases(code, Some("/")), code);
+    let input = "# Title\n\n![x](@docusaurus/useBaseUrl) `[x](@site/a.md)`\n";
+    let (converted, warnings) = convert_content_with_report(input, &ConvertOptions::default());
+    assert_eq!(converted, input);
+    let unresolved: Vec<_> = warnings.iter().filter(|w| w.kind == WarningKind::UnresolvedAlias).collect();
+    assert_eq!(unresolved.len(), 1);
+    assert_eq!(unresolved[0].line, Some(3));
+    assert!(unresolved[0].message.contains("@docusaurus/useBaseUrl"));
+}
+
+#[test]
+fn test_cli_json_summary() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join("good.mdx"), "# Good\n\n<Widget/>\n").unwrap();
+    std::fs::write(source.path().join("bad.md"), [0xff, 0xfe]).unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_doc2quarto"))
+        .arg("--source")
+        .arg(source.path())
+        .arg("--dest")
+        .arg(dest.path())
+        .arg("--json")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(summary["converted"], 1);
+    assert_eq!(summary["skipped"], 0);
+    assert_eq!(summary["failed"], 1);
+
+    let files = summary["files"].as_array().unwrap();
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0]["success"], false);
+    assert!(files[0]["error"].as_str().unwrap().contains("UTF-8"));
+    assert_eq!(files[1]["success"], true);
+    assert_eq!(files[1]["dest"], dest.path().join("good.qmd").to_str().unwrap());
+    assert_eq!(files[1]["bytes_written"], 18);
+    assert_eq!(files[1]["warnings"][0]["kind"], "residual_component");
+    assert_eq!(files[1]["warnings"][0]["line"], 3);
+}
+
+#[test]
+fn test_convert_content_indented_admonition_in_list() {
+    let input = "1. Install the tool\n\n   :::tip Shortcut\n   Use the installer\n   :::\n\n2. Run it\n";
+    assert_eq!(
+        convert_content(input),
+        "1. Install the tool\n\n   :::: {.callout-tip}\n   ## Shortcut\n   Use the installer\n   ::::\n\n2. Run it\n"
+    );
+    assert_eq!(convert_admonitions("  :::note"), "  :::: {.callout-note}");
+}
+
+#[test]
+fn test_convert_admonitions_space_after_colons() {
+    assert_eq!(convert_admonitions("::: note"), convert_admonitions(":::note"));
+    assert_eq!(convert_admonitions(":::  tip Title"), convert_admonitions(":::tip Title"));
+    assert_eq!(convert_content("::: warning\nCareful\n:::\n"), convert_content(":::warning\nCareful\n:::\n"));
+
+    // Already-converted Quarto fences are not admonitions
+    assert_eq!(convert_admonitions("::: {.panel-tabset}"), "::: {.panel-tabset}");
+}
+
+#[test]
+fn test_validate_frontmatter_catches_malformed_yaml() {
+    // Quoted colons are fine, an unquoted second colon is not
+    assert!(validate_frontmatter(&convert_frontmatter(&["title: \"Foo: Bar\""])).is_ok());
+    assert!(validate_frontmatter("").is_ok());
+    assert!(validate_frontmatter("title: Foo: Bar\n").is_err());
+
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let source_file = source.path().join("broken.md");
+    std::fs::write(&source_file, "---\ntitle: Foo: Bar\n---\n# Body\n").unwrap();
+
+    let opts = ConvertOptions { validate: true, ..ConvertOptions::default() };
+    let err = process_files(&source_file, source.path(), dest.path(), &opts).unwrap_err().to_string();
+    assert!(err.contains("broken.md"));
+    assert!(err.contains("not valid YAML"));
+    assert!(!dest.path().join("broken.qmd").exists());
+
+    // Without validation the frontmatter is passed through as before
+    process_files(&source_file, source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+    assert!(dest.path().join("broken.qmd").exists());
+}
+
+#[test]
+fn test_convert_code_fence_title_and_highlight() {
+    assert_eq!(convert_code_fence("```js title=\"example.js\""), "```{.js filename=\"example.js\"}");
+    assert_eq!(convert_code_fence("```python {1,3-5}"), "```{.python code-line-numbers=\"1,3-5\"}");
+    assert_eq!(
+        convert_code_fence("  ````jsx title='App.jsx' {2} showLineNumbers"),
+        "  ````{.jsx filename=\"App.jsx\" code-line-numbers=\"2\"}"
+    );
+    assert_eq!(convert_code_fence("```bash showLineNumbers"), "```{.bash code-line-numbers=\"true\"}");
+
+    // Plain fences are untouched
+    assert_eq!(convert_code_fence("```rust"), "```rust");
+    assert_eq!(convert_code_fence("```"), "```");
+
+    // Only the opening fence is rewritten
+    let input = "```js title=\"a.js\"\nconst x = {1};\n```\n";
+    assert_eq!(convert_content(input), "```{.js filename=\"a.js\"}\nconst x = {1};\n```\n");
+}
+
+#[test]
+fn test_convert_code_fence_title_with_quotes() {
+    assert_eq!(convert_code_fence("```sh title='say \"hi\"'"), "```{.sh filename=\"say \\\"hi\\\"\"}");
+    assert_eq!(convert_code_fence(r"```txt title='C:\temp'"), r#"```{.txt filename="C:\\temp"}"#);
+}
+
+#[test]
+fn test_convert_content_preserves_final_newline() {
+    assert_eq!(convert_content("# Title\nLast line"), "# Title\nLast line");
+    assert_eq!(convert_content("# Title\nLast line\n"), "# Title\nLast line\n");
+    assert_eq!(convert_content("# Title\n\n"), "# Title\n\n");
+    assert_eq!(convert_content(":::note\nBody\n:::"), ":::: {.callout-note}\nBody\n::::");
+    assert_eq!(convert_content(""), "");
+}
+
+#[test]
+fn test_convert_content_closes_frontmatter_block() {
+    let result = convert_content("---\ntitle: Page\nsidebar_position: 1\n---\n# Body\n");
+    assert_eq!(result, "---\ntitle: Page\norder: 1\n---\n# Body\n");
+
+    let toml = convert_content("+++\ntitle = \"Page\"\n+++\n\nText\n");
+    assert_eq!(toml, "---\ntitle: Page\n---\n\nText\n");
+
+    // An unterminated block is left as written
+    assert_eq!(convert_content("---\ntitle: Page\n"), "---\ntitle: Page\n");
+}
+
+#[test]
+fn test_convert_tree_exclude_glob_skips_subdirectory() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("docs")).unwrap();
+    std::fs::create_dir_all(source.path().join("blog").join("2024")).unwrap();
+    std::fs::write(source.path().join("docs").join("intro.md"), "# Intro\n").unwrap();
+    std::fs::write(source.path().join("blog").join("post.md"), "# Post\n").unwrap();
+    std::fs::write(source.path().join("blog").join("2024").join("recap.mdx"), "# Recap\n").unwrap();
+    let opts = ConvertOptions { exclude: vec!["blog/**".to_string()], ..ConvertOptions::default() };
+
+    let (files, filtered_out) = collect_filtered_source_files(source.path(), &opts).unwrap();
+    assert_eq!(files, vec![source.path().join("docs").join("intro.md")]);
+    assert_eq!(filtered_out, 2);
+
+    let reports = convert_tree(source.path(), dest.path(), &opts).unwrap();
+    assert_eq!(reports.len(), 1);
+    assert!(dest.path().join("docs").join("intro.qmd").exists());
+    assert!(!dest.path().join("blog").exists());
+
+    let include = ConvertOptions { include: vec!["blog/**/*.mdx".to_string()], ..ConvertOptions::default() };
+    let (files, filtered_out) = collect_filtered_source_files(source.path(), &include).unwrap();
+    assert_eq!(files, vec![source.path().join("blog").join("2024").join("recap.mdx")]);
+    assert_eq!(filtered_out, 2);
+
+    let invalid = ConvertOptions { exclude: vec!["blog/[".to_string()], ..ConvertOptions::default() };
+    assert!(collect_filtered_source_files(source.path(), &invalid).is_err());
+}
+
+#[test]
+fn test_convert_admonitions_single_line() {
+    assert_eq!(
+        convert_admonitions(":::note Some text :::"),
+        ":::: {.callout-note}\nSome text\n::::"
+    );
+    assert_eq!(
+        convert_admonition_fence(":::tip[Heads up] Read this first:::", CALLOUT_FENCE_WIDTH, CalloutTitleStyle::Attribute),
+        ":::: {.callout-tip title=\"Heads up\"}\nRead this first\n::::"
+    );
+
+    // The one-liner leaves nothing open, so the following block is not nested
+    let content = ":::danger Do not [delete](setup.md) this :::\n\n:::note\nBody\n:::\n";
+    assert_eq!(
+        convert_content(content),
+        ":::: {.callout-important}\nDo not [delete](setup.qmd) this\n::::\n\n:::: {.callout-note}\nBody\n::::\n"
+    );
+}
+
+#[test]
+fn test_converter_builder_convert_file() {
+    let converter = Converter::new().with_link_rewriting(false).with_image_copy(false);
+    assert!(!converter.options().convert_links);
+    assert!(!converter.options().copy_images);
+    assert_eq!(converter.convert_str("[Setup](setup.md)\n"), "[Setup](setup.md)\n");
+
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let file = source.path().join("intro.md");
+    std::fs::write(&file, ":::info\nSee [Setup](setup.md)\n:::\n").unwrap();
+
+    let report = converter.convert_file(&file, source.path(), dest.path()).unwrap();
+    assert!(report.is_success());
+    assert_eq!(
+        std::fs::read_to_string(dest.path().join("intro.qmd")).unwrap(),
+        ":::: {.callout-note}\nSee [Setup](setup.md)\n::::\n"
+    );
+}
+
+#[test]
+fn test_convert_tree_check_links_reports_dangling_link() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("guide")).unwrap();
+    std::fs::write(
+        source.path().join("intro.md"),
+        "# Intro\n\nSee [Setup](./guide/setup.md#install).\nAnd [Gone](missing.md).\n",
+    )
+    .unwrap();
+    std::fs::write(source.path().join("guide").join("setup.md"), "Back to [Intro](../intro.md)\n").unwrap();
+    let opts = ConvertOptions { check_links: true, dry_run: true, ..ConvertOptions::default() };
+
+    let reports = convert_tree(source.path(), dest.path(), &opts).unwrap();
+    let intro = reports.iter().find(|r| r.source_path.ends_with("intro.md")).unwrap();
+    assert_eq!(intro.broken_links, vec![BrokenLink { line: 4, target: "missing.qmd".to_string() }]);
+    assert!(reports.iter().all(|r| r.source_path.ends_with("intro.md") || r.broken_links.is_empty()));
+
+    // Without the option nothing is checked
+    let reports = convert_tree(source.path(), dest.path(), &dry_run()).unwrap();
+    assert!(reports.iter().all(|r| r.broken_links.is_empty()));
+}
+
+#[test]
+fn test_convert_tree_flatten_disambiguates_collisions() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("guide")).unwrap();
+    std::fs::create_dir_all(source.path().join("api")).unwrap();
+    std::fs::write(source.path().join("guide").join("intro.md"), "# Guide\n").unwrap();
+    std::fs::write(source.path().join("api").join("intro.md"), "# API\n").unwrap();
+    std::fs::write(source.path().join("api").join("client.md"), "# Client\n").unwrap();
+    let opts = ConvertOptions { flatten: true, ..ConvertOptions::default() };
+
+    let reports = convert_tree(source.path(), dest.path(), &opts).unwrap();
+    assert!(reports.iter().all(|r| r.is_success()));
+
+    let mut written: Vec<String> = std::fs::read_dir(dest.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    written.sort();
+    assert_eq!(written, vec!["api-intro.qmd", "client.qmd", "guide-intro.qmd"]);
+    assert_eq!(std::fs::read_to_string(dest.path().join("guide-intro.qmd")).unwrap(), "# Guide\n");
+}
+
+#[test]
+fn test_convert_tree_writes_readme_as_index() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("guide")).unwrap();
+    std::fs::write(source.path().join("guide").join("README.md"), "# Guide\n").unwrap();
+    std::fs::write(source.path().join("guide").join("setup.md"), "# Setup\n").unwrap();
+
+    let reports = convert_tree(source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+    assert!(reports.iter().all(|r| r.is_success()));
+    assert_eq!(std::fs::read_to_string(dest.path().join("guide").join("index.qmd")).unwrap(), "# Guide\n");
+    assert!(!dest.path().join("guide").join("README.qmd").exists());
+
+    // Configurable, and a single file is named the same way
+    let opts = ConvertOptions { readme_as_index: false, ..dry_run() };
+    let plan = plan_conversion(source.path(), dest.path(), &opts).unwrap();
+    assert_eq!(plan[0].1, dest.path().join("guide").join("README.qmd"));
+    let report = convert_file(&source.path().join("guide").join("README.md"), &dest.path().join("single"), &dry_run()).unwrap();
+    assert_eq!(report.dest_path, dest.path().join("single").join("index.qmd"));
+}
+
+#[test]
+fn test_links_into_readme_folders_follow_the_rename() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    for folder in ["guide", "api"] {
+        std::fs::create_dir_all(source.path().join(folder)).unwrap();
+        std::fs::write(source.path().join(folder).join("README.md"), "# Readme\n").unwrap();
+    }
+    // The api README collides with its index page and keeps its name
+    std::fs::write(source.path().join("api").join("index.md"), "# API\n").unwrap();
+    std::fs::write(source.path().join("intro.md"), "[Guide](./guide/README.md#start) and [API](api/README.md)\n").unwrap();
+    std::fs::write(source.path().join("guide").join("next.md"), "[Back](README.md)\n").unwrap();
+
+    let opts = ConvertOptions { check_links: true, ..ConvertOptions::default() };
+    let reports = convert_tree(source.path(), dest.path(), &opts).unwrap();
+    assert!(reports.iter().all(|r| r.is_success() && r.broken_links.is_empty()));
+    assert_eq!(
+        std::fs::read_to_string(dest.path().join("intro.qmd")).unwrap(),
+        "[Guide](./guide/index.qmd#start) and [API](api/README.qmd)\n"
+    );
+    assert_eq!(std::fs::read_to_string(dest.path().join("guide").join("next.qmd")).unwrap(), "[Back](index.qmd)\n");
+
+    let keep = ConvertOptions { readme_as_index: false, ..opts };
+    convert_tree(source.path(), dest.path(), &keep).unwrap();
+    assert!(std::fs::read_to_string(dest.path().join("intro.qmd")).unwrap().contains("(./guide/README.qmd#start)"));
+}
+
+#[test]
+fn test_convert_tree_readme_next_to_index_keeps_its_name() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join("README.md"), "# Readme\n").unwrap();
+    std::fs::write(source.path().join("index.md"), "# Index\n").unwrap();
+
+    let reports = convert_tree(source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+    assert!(reports.iter().all(|r| r.is_success()));
+    assert_eq!(std::fs::read_to_string(dest.path().join("index.qmd")).unwrap(), "# Index\n");
+    assert_eq!(std::fs::read_to_string(dest.path().join("README.qmd")).unwrap(), "# Readme\n");
+}
+
+#[test]
+fn test_convert_admonitions_details_collapse() {
+    assert_eq!(
+        convert_admonitions(":::details Show the output"),
+        ":::: {.callout-note collapse=\"true\" title=\"Show the output\"}"
+    );
+    assert_eq!(convert_admonitions(":::details"), ":::: {.callout-note collapse=\"true\"}");
+    assert_eq!(
+        convert_content(":::details[Why?]\nBecause.\n:::\n"),
+        ":::: {.callout-note collapse=\"true\" title=\"Why?\"}\nBecause.\n::::\n"
+    );
+}
+
+#[test]
+fn test_conversion_error_variants() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+
+    let missing = source.path().join("missing.md");
+    let err = process_files(&missing, source.path(), dest.path(), &dry_run()).unwrap_err();
+    assert!(matches!(&err, ConversionError::ReadFailed { path, .. } if *path == missing));
+
+    let outside = dest.path().join("outside.md");
+    std::fs::write(&outside, "# Outside\n").unwrap();
+    let err = process_files(&outside, source.path(), dest.path(), &dry_run()).unwrap_err();
+    assert!(matches!(err, ConversionError::StripPrefixFailed { .. }));
+
+    let binary = source.path().join("binary.md");
+    std::fs::write(&binary, b"ok\xff").unwrap();
+    let err = process_files(&binary, source.path(), dest.path(), &dry_run()).unwrap_err();
+    assert!(matches!(err, ConversionError::InvalidUtf8 { offset: 2, .. }));
+
+    // A regular file where the output directory should be
+    let blocked = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("guide")).unwrap();
+    std::fs::write(source.path().join("guide").join("setup.md"), "# Setup\n").unwrap();
+    std::fs::write(blocked.path().join("guide"), "").unwrap();
+    let err = process_files(&source.path().join("guide").join("setup.md"), source.path(), blocked.path(), &ConvertOptions::default())
+        .unwrap_err();
+    assert!(matches!(err, ConversionError::CreateDirFailed { .. }));
+
+    let err = convert_tree(&source.path().join("nope"), dest.path(), &dry_run()).unwrap_err();
+    assert!(matches!(err, ConversionError::SourceNotFound { .. }));
+}
+
+#[test]
+fn test_convert_tree_progress_advances_once_per_failing_file() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join("good.md"), "# Good\n").unwrap();
+    std::fs::write(source.path().join("bad.md"), [0xff, 0xfe, 0xfd]).unwrap();
+    std::fs::write(source.path().join("worse.md"), [0xc3]).unwrap();
+
+    let pb = indicatif::ProgressBar::hidden();
+    pb.set_length(3);
+    let failed = std::sync::atomic::AtomicUsize::new(0);
+    let reports = convert_tree_with_progress(source.path(), dest.path(), &dry_run(), |report| {
+        if !report.is_success() {
+            failed.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+        pb.inc(1);
+    })
+    .unwrap();
+
+    assert_eq!(pb.position(), 3);
+    assert_eq!(failed.into_inner(), 2);
+    assert_eq!(reports.iter().filter(|r| !r.is_success()).count(), 2);
+}
+
+#[test]
+fn test_convert_content_admonition_component() {
+    let input = "import Admonition from '@theme/Admonition';\n\n<Admonition type=\"tip\" title=\"Pro tip\">\nUse the CLI.\n</Admonition>\n\n<Admonition\n  type=\"danger\">\nCareful!</Admonition>\n\n<Admonition type=\"info\">Short note</Admonition>\n";
+    assert_eq!(
+        convert_content(input),
+        "\n:::: {.callout-tip}\n## Pro tip\nUse the CLI.\n::::\n\n:::: {.callout-important}\nCareful!\n::::\n\n:::: {.callout-note}\nShort note\n::::\n"
+    );
+    assert!(find_jsx_components(&convert_content(input)).is_empty());
+}
+
+#[test]
+fn test_convert_links_skips_inline_code() {
+    assert_eq!(convert_links("Use `[x](a.md)` syntax, see [y](b.md)."), "Use `[x](a.md)` syntax, see [y](b.qmd).");
+    assert_eq!(convert_links("Use ``[x](a.md)`` syntax"), "Use ``[x](a.md)`` syntax");
+
+    let input = "Use `[x](a.md)` syntax\n";
+    let (converted, warnings) = convert_content_with_report(input, &ConvertOptions::default());
+    assert_eq!(converted, input);
+    assert!(!warnings.iter().any(|w| w.kind == WarningKind::RewrittenLink));
+}
+
+#[test]
+fn test_component_props_are_read_in_one_pass() {
+    // A prop-like text inside another prop's value is not a prop
+    let input = "<Admonition title=\"Set type='danger' here\" type=\"tip\">\nBody\n</Admonition>\n";
+    assert_eq!(convert_content(input), ":::: {.callout-tip}\n## Set type='danger' here\nBody\n::::\n");
+}
+
+#[test]
+fn test_convert_content_with_report_warnings() {
+    let input = "# Title\n\n:::mystery\nBody with [a link](./next.md)\n:::\n\n<Widget />\n";
+    let (converted, warnings) = convert_content_with_report(input, &ConvertOptions::default());
+
+    assert_eq!(converted, convert_content(input));
+    let unknown = warnings.iter().find(|w| w.kind == WarningKind::UnknownAdmonition).unwrap();
+    assert_eq!(unknown.line, Some(3));
+    assert!(unknown.to_string().starts_with("Line 3: unknown admonition type `mystery`"));
+    assert!(warnings.iter().any(|w| w.kind == WarningKind::RewrittenLink && w.line == Some(4)));
+    assert!(warnings.iter().any(|w| w.kind == WarningKind::ResidualComponent && w.line == Some(7)));
+
+    let (_, warnings) = convert_content_with_report(":::note\nFine\n:::\n", &ConvertOptions::default());
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_frontmatter_image_made_relative_to_document() {
+    let metadata = serde_yaml::Mapping::new();
+    let lines = vec!["title: Launch", "image: /img/social-card.png"];
+    let nested = FrontmatterOptions { metadata: &metadata, doc_id: DocId::Drop, site_root: "../../", dropped_keys: DEFAULT_DROPPED_KEYS, authors: &serde_yaml::Mapping::new() };
+    assert!(convert_frontmatter_with_options(&lines, &nested).contains("image: ../../img/social-card.png"));
+    assert!(convert_frontmatter(&lines).contains("image: img/social-card.png"));
+    assert!(convert_frontmatter(&["image: https://example.com/card.png"]).contains("image: https://example.com/card.png"));
+
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("blog").join("2024")).unwrap();
+    std::fs::write(source.path().join("blog").join("2024").join("launch.md"), "---\nimage: /img/card.png\n---\n# Launch\n").unwrap();
+    convert_tree(source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+    let converted = std::fs::read_to_string(dest.path().join("blog").join("2024").join("launch.qmd")).unwrap();
+    assert!(converted.contains("image: ../../img/card.png"));
+}
+
+#[test]
+fn test_apply_change_reconverts_and_removes() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("guide")).unwrap();
+    let page = source.path().join("guide").join("setup.md");
+    std::fs::write(&page, "# Setup\n").unwrap();
+    let opts = ConvertOptions::default();
+    convert_tree(source.path(), dest.path(), &opts).unwrap();
+
+    // An edit is picked up and converted again
+    std::fs::write(&page, ":::tip\nEdited\n:::\n").unwrap();
+    let update = apply_change(&page, source.path(), dest.path(), &opts).unwrap();
+    assert!(matches!(update, Some(WatchUpdate::Converted(ref report)) if report.is_success()));
+    let output = dest.path().join("guide").join("setup.qmd");
+    assert_eq!(std::fs::read_to_string(&output).unwrap(), ":::: {.callout-tip}\nEdited\n::::\n");
+
+    // Non-markdown files are ignored
+    let image = source.path().join("guide").join("diagram.png");
+    std::fs::write(&image, "png").unwrap();
+    assert!(apply_change(&image, source.path(), dest.path(), &opts).unwrap().is_none());
+
+    // Deleting the source removes its output
+    std::fs::remove_file(&page).unwrap();
+    let update = apply_change(&page, source.path(), dest.path(), &opts).unwrap();
+    assert!(matches!(update, Some(WatchUpdate::Removed(ref path)) if *path == output));
+    assert!(!output.exists());
+}
+
+#[test]
+fn test_apply_change_uses_flattened_names() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    for folder in ["guide", "api"] {
+        std::fs::create_dir_all(source.path().join(folder)).unwrap();
+        std::fs::write(source.path().join(folder).join("intro.md"), format!("# {}\n", folder)).unwrap();
+    }
+    let opts = ConvertOptions { flatten: true, ..ConvertOptions::default() };
+    convert_tree(source.path(), dest.path(), &opts).unwrap();
+
+    // Colliding names keep the parent prefix the full conversion gave them
+    let guide = source.path().join("guide").join("intro.md");
+    std::fs::write(&guide, "# Guide, edited\n").unwrap();
+    let update = apply_change(&guide, source.path(), dest.path(), &opts).unwrap();
+    assert!(matches!(update, Some(WatchUpdate::Converted(ref report)) if report.dest_path == dest.path().join("guide-intro.qmd")));
+    assert_eq!(std::fs::read_to_string(dest.path().join("guide-intro.qmd")).unwrap(), "# Guide, edited\n");
+    assert_eq!(std::fs::read_to_string(dest.path().join("api-intro.qmd")).unwrap(), "# api\n");
+    assert!(!dest.path().join("intro.qmd").exists());
+
+    std::fs::remove_file(source.path().join("api").join("intro.md")).unwrap();
+    let update = apply_change(&source.path().join("api").join("intro.md"), source.path(), dest.path(), &opts).unwrap();
+    assert!(matches!(update, Some(WatchUpdate::Removed(ref path)) if *path == dest.path().join("api-intro.qmd")));
+    assert!(dest.path().join("guide-intro.qmd").exists());
+}
+
+#[test]
+fn test_convert_content_html_comments() {
+    let input = "# Title\n\n<!-- TODO: expand this section -->\n<!-- prettier-ignore -->\n:::note\nKept\n:::\n\n<!--\n:::warning\nCommented out, see [old](old.md)\n:::\n-->\n";
+    assert_eq!(
+        convert_content(input),
+        "# Title\n\n<!-- TODO: expand this section -->\n:::: {.callout-note}\nKept\n::::\n\n<!--\n:::warning\nCommented out, see [old](old.md)\n:::\n-->\n"
+    );
+    assert!(is_comment_directive("  <!--prettier-ignore-start-->"));
+    assert!(!is_comment_directive("<!-- prettier is great -->"));
+}
+
+#[test]
+fn test_convert_tabs_non_ascii_whitespace_blank_line() {
+    let input = "<Tabs>\n<TabItem value=\"a\">\n  First\n\u{a0}\u{a0}\n  Second\n</TabItem>\n</Tabs>\n";
+    let output = convert_tabs(input);
+    assert!(output.contains("## a\n\nFirst\n\nSecond\n"));
+}
+
+#[test]
+fn test_convert_content_tabs_inside_admonition() {
+    let input = "\
+:::note
+<Tabs>
+<TabItem value=\"a\">Apple</TabItem>
+</Tabs>
+After the tabs
+:::
+";
+    let (result, warnings) = convert_content_with_report(input, &ConvertOptions::default());
+    assert_eq!(
+        result,
+        ":::: {.callout-note}\n::: {.panel-tabset}\n\n## a\n\nApple\n\n:::\nAfter the tabs\n::::\n"
+    );
+    assert!(warnings.is_empty());
+
+    // A standalone tabset is not an unmatched admonition close either
+    let (_, warnings) = convert_content_with_report("<Tabs>\n<TabItem value=\"a\">A</TabItem>\n</Tabs>\n", &ConvertOptions::default());
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_convert_tabs_group_id() {
+    let input = "\
+<Tabs groupId=\"os\">
+<TabItem value=\"mac\" label=\"macOS\">brew install</TabItem>
+<TabItem value=\"linux\" label=\"Linux\">apt install</TabItem>
+</Tabs>
+
+<Tabs
+  groupId=\"os\"
+  defaultValue=\"linux\">
+<TabItem value=\"mac\" label=\"macOS\">~/Library</TabItem>
+<TabItem value=\"linux\" label=\"Linux\">~/.config</TabItem>
+</Tabs>
+";
+    let output = convert_tabs(input);
+    assert_eq!(output.matches("::: {.panel-tabset group=\"os\"}").count(), 2);
+    assert!(!output.contains("groupId"));
+    assert!(output.contains("## Linux\n\n~/.config\n"));
+    assert!(convert_tabs("<Tabs>\n</Tabs>\n").starts_with("::: {.panel-tabset}\n"));
+}
+
+#[test]
+fn test_rewritten_paths_use_forward_slashes() {
+    assert_eq!(convert_links("[Setup](..\\guide\\setup.md#install)"), "[Setup](../guide/setup.qmd#install)");
+
+    // A target built from native path components, backslashes on Windows
+    let native = std::path::Path::new("..").join("guide").join("setup.md");
+    let line = format!("[Setup]({})", native.display());
+    assert_eq!(convert_links(&line), "[Setup](../guide/setup.qmd)");
+
+    assert_eq!(rewrite_image_target("..\\img\\diagram.png", &["img"]), Some("img/diagram.png".to_string()));
+}
+
+#[test]
+fn test_diff_reports_changed_lines_without_writing() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let source_file = source.path().join("intro.md");
+    std::fs::write(&source_file, "# Intro\n\nNew text\n").unwrap();
+    let dest_file = dest.path().join("intro.qmd");
+    std::fs::write(&dest_file, "# Intro\n\nOld text\n").unwrap();
+
+    let opts = ConvertOptions { diff: true, ..ConvertOptions::default() };
+    let report = process_files(&source_file, source.path(), dest.path(), &opts).unwrap();
+
+    let diff = report.diff.unwrap();
+    assert!(diff.contains("\n-Old text\n"));
+    assert!(diff.contains("\n+New text\n"));
+    assert!(!diff.contains("-# Intro"));
+    assert_eq!(std::fs::read_to_string(&dest_file).unwrap(), "# Intro\n\nOld text\n");
+}
+
+#[test]
+fn test_convert_content_empty_inputs() {
+    assert_eq!(convert_content(""), "");
+    assert_eq!(convert_content("   \n\t\n"), "   \n\t\n");
+    assert_eq!(convert_content("---\n---\n"), "---\n---\n");
+    assert_eq!(convert_content("---\n---"), "---\n---");
+    assert_eq!(convert_content("---\n  \n---\n"), "---\n---\n");
+    assert!(validate_frontmatter("").is_ok());
+
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let source_file = source.path().join("empty.md");
+    std::fs::write(&source_file, "").unwrap();
+    let opts = ConvertOptions { validate: true, ..ConvertOptions::default() };
+    let report = process_files(&source_file, source.path(), dest.path(), &opts).unwrap();
+    assert_eq!(report.bytes_written, 0);
+    assert_eq!(std::fs::read_to_string(dest.path().join("empty.qmd")).unwrap(), "");
+}
+
+#[test]
+fn test_convert_content_reference_style_links() {
+    let input = "\
+See the [setup guide][setup] and [API][].
+
+[setup]: ../guide/setup.md#install \"Setup\"
+[API]: <./api reference.mdx>
+[site]: https://example.com/page.md
+[^1]: Footnote linking [intro](./intro.md).
+";
+    let output = convert_content(input);
+    assert!(output.contains("See the [setup guide][setup] and [API][].\n"));
+    assert!(output.contains("[setup]: ../guide/setup.qmd#install \"Setup\"\n"));
+    assert!(output.contains("[API]: <./api reference.qmd>\n"));
+    assert!(output.contains("[site]: https://example.com/page.md\n"));
+    assert!(output.contains("[^1]: Footnote linking [intro](./intro.qmd).\n"));
+}
+
+#[test]
+fn test_convert_tree_output_extension() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("guide")).unwrap();
+    std::fs::write(source.path().join("intro.md"), "See [setup](./guide/setup.md).\n").unwrap();
+    std::fs::write(source.path().join("guide").join("setup.mdx"), "# Setup\n").unwrap();
+
+    let opts = ConvertOptions { output_extension: "md".to_string(), check_links: true, ..ConvertOptions::default() };
+    let reports = convert_tree(source.path(), dest.path(), &opts).unwrap();
+
+    assert!(reports.iter().all(|r| r.is_success() && r.broken_links.is_empty()));
+    assert_eq!(std::fs::read_to_string(dest.path().join("intro.md")).unwrap(), "See [setup](./guide/setup.md).\n");
+    assert!(dest.path().join("guide").join("setup.md").exists());
+    assert!(!dest.path().join("intro.qmd").exists());
+
+    assert!(validate_output_extension("Rmd").is_ok());
+    for invalid in ["", "qmd/x", "..\\qmd", "tar.gz"] {
+        let opts = ConvertOptions { output_extension: invalid.to_string(), ..dry_run() };
+        assert!(matches!(
+            convert_tree(source.path(), dest.path(), &opts),
+            Err(ConversionError::InvalidExtension { .. })
+        ));
+    }
+}
+
+#[test]
+fn test_convert_frontmatter_drops_docusaurus_only_keys() {
+    let lines = vec![
+        "title: Intro",
+        "hide_title: true",
+        "hide_table_of_contents: true",
+        "pagination_next: guide/setup",
+        "pagination_prev: null",
+        "custom_edit_url: https://example.com/edit",
+    ];
+    assert_eq!(convert_frontmatter(&lines), "title: Intro\ntoc: false\n");
+
+    let metadata = serde_yaml::Mapping::new();
+    let keep_title = FrontmatterOptions { metadata: &metadata, doc_id: DocId::Drop, site_root: "", dropped_keys: &["custom_edit_url"], authors: &serde_yaml::Mapping::new() };
+    assert_eq!(
+        convert_frontmatter_with_options(&["hide_title: true", "custom_edit_url: x"], &keep_title),
+        "hide_title: true\n"
+    );
+
+    let config = Config::from_toml_str("[frontmatter]\ndrop = [\"hide_title\"]\n").unwrap();
+    assert_eq!(config.dropped_keys, Some(vec!["hide_title".to_string()]));
+    assert!(Config::from_toml_str("[frontmatter]\ndrop = \"hide_title\"\n").is_err());
+}
+
+#[test]
+fn test_convert_frontmatter_hide_table_of_contents_to_toc() {
+    assert_eq!(convert_frontmatter(&["title: A", "hide_table_of_contents: true"]), "title: A\ntoc: false\n");
+    assert_eq!(convert_frontmatter(&["title: A", "hide_table_of_contents: false"]), "title: A\n");
+    assert_eq!(convert_frontmatter(&["title: A"]), "title: A\n");
+    // An explicit Quarto setting is kept over the Docusaurus one
+    assert_eq!(convert_frontmatter(&["hide_table_of_contents: true", "toc: true"]), "toc: true\n");
+
+    // Not affected by a configured drop list
+    let metadata = serde_yaml::Mapping::new();
+    let opts = FrontmatterOptions { metadata: &metadata, doc_id: DocId::Drop, site_root: "", dropped_keys: &[], authors: &serde_yaml::Mapping::new() };
+    assert_eq!(convert_frontmatter_with_options(&["hide_table_of_contents: false"], &opts), "");
+}
+
+#[test]
+fn test_line_transforms_run_before_builtin_passes() {
+    let converter = Converter::new()
+        .with_line_transform(|line| line.contains("@@VERSION@@").then(|| line.replace("@@VERSION@@", "2.0")))
+        .with_line_transform(|line| line.strip_prefix("!! ").map(|text| format!(":::warning\n{}\n:::", text)));
+
+    let output = converter.convert_str("Version @@VERSION@@, see [notes](notes.md)\n!! Breaking @@VERSION@@ change\n");
+    assert_eq!(output, "Version 2.0, see [notes](notes.qmd)\n:::: {.callout-warning}\nBreaking 2.0 change\n::::\n");
+
+    // No trailing newline is added to content that had none
+    assert_eq!(converter.convert_str("@@VERSION@@"), "2.0");
+    assert_eq!(converter.options().clone(), *converter.options());
+}
+
+#[test]
+fn test_convert_content_math_delimiters() {
+    let input = r"Energy is \( E = mc^2 \) and costs $5 or $10.
+Already $x^2$ and $$y$$.
+
+\[
+\int_0^1 x \, dx
+\]
+
+\[ a^2 + b^2 = c^2 \]
+
+Escaped arr\[0\] stays, as does `\(code\)`.
+
+```latex
+\(kept\)
+```
+";
+    let expected = r"Energy is $E = mc^2$ and costs $5 or $10.
+Already $x^2$ and $$y$$.
+
+$$
+\int_0^1 x \, dx
+$$
+
+$$a^2 + b^2 = c^2$$
+
+Escaped arr\[0\] stays, as does `\(code\)`.
+
+```latex
+\(kept\)
+```
+";
+    assert_eq!(convert_content(input), expected);
+}
+
+#[test]
+fn test_inline_code_with_double_backticks_is_kept() {
+    // A double-backtick span may hold single backticks and ends at the next double run
+    assert_eq!(convert_math_delimiters(r"``a `\(x\)` b`` and \(y\)"), r"``a `\(x\)` b`` and $y$");
+    assert_eq!(convert_footnotes("``[^not `a` note]`` and [^a real note]"), "``[^not `a` note]`` and ^[a real note]");
+    // An unmatched run is literal text
+    assert_eq!(convert_math_delimiters(r"``` \(x\)"), "``` $x$");
+    assert!(find_jsx_components("Write ``<Tabs>` ``, then <Card />.").iter().map(|c| c.name.as_str()).eq(["Card"]));
+}
+
+/// Converts every `tests/fixtures/golden/<case>/input.md` and compares it with
+/// the `expected.qmd` next to it. Run with `UPDATE_GOLDEN=1` to rewrite the
+/// expectations from the current output.
+#[test]
+fn test_golden_fixtures() {
+    let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join("golden");
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+
+    let mut cases: Vec<_> = std::fs::read_dir(&root).unwrap().map(|entry| entry.unwrap().path()).collect();
+    cases.sort();
+    assert!(!cases.is_empty(), "no golden fixtures in {:?}", root);
+
+    let mut mismatched = Vec::new();
+    for case in &cases {
+        let input = std::fs::read_to_string(case.join("input.md")).unwrap();
+        let actual = convert_content(&input);
+        let expected_path = case.join("expected.qmd");
+
+        if update {
+            std::fs::write(&expected_path, &actual).unwrap();
+            continue;
+        }
+        let expected = std::fs::read_to_string(&expected_path)
+            .unwrap_or_else(|e| panic!("{:?}: {} (run with UPDATE_GOLDEN=1 to create it)", expected_path, e));
+        if actual != expected {
+            eprintln!("--- {:?}\n{}", case, similar::TextDiff::from_lines(&expected, &actual).unified_diff());
+            mismatched.push(case.file_name().unwrap().to_string_lossy().into_owned());
+        }
+    }
+
+    assert!(mismatched.is_empty(), "golden output differs for {:?}, rerun with UPDATE_GOLDEN=1 if intended", mismatched);
+}
+
+#[test]
+fn test_convert_frontmatter_draft_and_unlisted() {
+    assert_eq!(convert_frontmatter(&["title: A", "draft: true"]), "title: A\ndraft: true\n");
+    assert_eq!(convert_frontmatter(&["title: A", "unlisted: true"]), "title: A\ndraft: true\n");
+    assert_eq!(convert_frontmatter(&["title: A", "unlisted: false"]), "title: A\n");
+    // An explicit draft setting wins over unlisted
+    assert_eq!(convert_frontmatter(&["draft: false", "unlisted: true"]), "draft: false\n");
+}
+
+#[test]
+fn test_process_files_strips_byte_order_mark() {
+    assert_eq!(convert_content("\u{feff}---\nsidebar_position: 1\n---\n# Hi\n"), "---\norder: 1\n---\n# Hi\n");
+    assert_eq!(convert_content("\u{feff}:::tip\nX\n:::"), ":::: {.callout-tip}\nX\n::::");
+
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let source_file = source.path().join("windows.md");
+    std::fs::write(&source_file, b"\xEF\xBB\xBF---\r\ntitle: Windows\r\n---\r\nBody\r\n").unwrap();
+
+    process_files(&source_file, source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+    let output = std::fs::read_to_string(dest.path().join("windows.qmd")).unwrap();
+    assert!(output.starts_with("---\ntitle: Windows\n---\n"), "{:?}", output);
+}
+
+#[test]
+fn test_warnings_aggregated_by_kind() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join("a.md"), ":::hint\nA\n:::\n\n:::secret\nB\n:::\n").unwrap();
+    std::fs::write(source.path().join("b.mdx"), ":::hint\nC\n:::\n\n<Widget/>\n[Setup](setup.md)\n").unwrap();
+
+    let reports = convert_tree(source.path(), dest.path(), &ConvertOptions { check_links: true, ..dry_run() }).unwrap();
+    let counts = count_warnings(reports.iter().flat_map(|r| &r.warnings));
+    assert_eq!(counts[&WarningKind::UnknownAdmonition], 3);
+    assert_eq!(counts[&WarningKind::ResidualComponent], 1);
+    assert_eq!(counts[&WarningKind::BrokenLink], 1);
+    assert!(!counts.contains_key(&WarningKind::RewrittenLink));
+    assert_eq!(
+        format_warning_counts(&counts),
+        "3 unknown admonition type(s), 1 residual component(s), 1 broken link(s)"
+    );
+
+    let run = |verbose: bool| {
+        let mut command = std::process::Command::new(env!("CARGO_BIN_EXE_doc2quarto"));
+        command.arg("-s").arg(source.path()).arg("-d").arg(dest.path()).arg("--dry-run");
+        if verbose {
+            command.arg("--verbose");
+        }
+        command.output().unwrap()
+    };
+    let quiet = run(false);
+    assert!(String::from_utf8_lossy(&quiet.stdout).contains("Warnings: 3 unknown admonition type(s), 1 residual component(s)"));
+    assert!(!String::from_utf8_lossy(&quiet.stderr).contains("unknown admonition type `hint`"));
+    assert!(String::from_utf8_lossy(&run(true).stderr).contains("unknown admonition type `hint`"));
+}
+
+#[test]
+fn test_convert_frontmatter_blog_authors() {
+    let inline = convert_frontmatter(&[
+        "authors:",
+        "  - name: Jane Doe",
+        "    title: Maintainer",
+        "    url: https://example.com/jane",
+        "    image_url: https://example.com/jane.png",
+    ]);
+    assert_eq!(inline, "author:\n- name: Jane Doe\n  note: Maintainer\n  url: https://example.com/jane\n");
+
+    let authors_file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(authors_file.path(), "jane:\n  name: Jane Doe\n  email: jane@example.com\n  socials:\n    github: jane\n").unwrap();
+    let opts = ConvertOptions { authors: load_authors(authors_file.path()).unwrap(), ..ConvertOptions::default() };
+    let invalid = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(invalid.path(), "- jane\n").unwrap();
+    assert!(matches!(load_authors(invalid.path()), Err(ConversionError::InvalidAuthors { ref path, .. }) if path == invalid.path()));
+    assert!(matches!(load_authors(std::path::Path::new("missing-authors.yml")), Err(ConversionError::ReadFailed { .. })));
+    assert_eq!(
+        convert_content_with_options("---\nauthors: [jane, guest]\n---\n", &opts),
+        "---\nauthor:\n- name: Jane Doe\n  email: jane@example.com\n- guest\n---\n"
+    );
+    assert_eq!(convert_content_with_options("---\nauthors: jane\n---\n", &opts), "---\nauthor:\n- name: Jane Doe\n  email: jane@example.com\n---\n");
+
+    // Legacy single-author fields
+    assert_eq!(
+        convert_frontmatter(&["author: Jane", "author_title: Maintainer", "author_url: https://example.com"]),
+        "author:\n  name: Jane\n  note: Maintainer\n  url: https://example.com\n"
+    );
+
+    // `authors` wins over a legacy `author`, in either order
+    for lines in [["author: Old Name", "authors: [Jane Doe]"], ["authors: [Jane Doe]", "author: Old Name"]] {
+        assert_eq!(convert_frontmatter(&lines), "author:\n- Jane Doe\n");
+    }
+}
+
+#[test]
+fn test_cli_converts_stdin_to_stdout() {
+    use std::io::Write;
+
+    let run = |args: &[&str]| {
+        let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_doc2quarto"))
+            .args(args)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        // Without stdin mode the process may exit before reading its input
+        let _ = child.stdin.take().unwrap().write_all(b"---\nsidebar_position: 3\n---\n:::tip\nSee [setup](setup.md)\n:::\n");
+        child.wait_with_output().unwrap()
+    };
+
+    let expected = "---\norder: 3\n---\n:::: {.callout-tip}\nSee [setup](setup.qmd)\n::::\n";
+    for args in [&["--stdin"][..], &["--stdout", "--no-links"]] {
+        let output = run(args);
+        assert_eq!(output.status.code(), Some(0));
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        if args.contains(&"--no-links") {
+            assert!(stdout.contains("[setup](setup.md)"));
+        } else {
+            assert_eq!(stdout, expected);
+        }
+    }
+
+    // Piped input alone does not select stdin mode, the missing paths are an error
+    let output = run(&[]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_copied_images_get_normalized_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("img")).unwrap();
+    let image = source.path().join("img").join("generated.png");
+    std::fs::write(&image, b"png").unwrap();
+    std::fs::set_permissions(&image, std::fs::Permissions::from_mode(0o700)).unwrap();
+    let source_file = source.path().join("intro.md");
+    std::fs::write(&source_file, "![Plot](img/generated.png)\n").unwrap();
+
+    let mode_of = |path: &std::path::Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o7777;
+
+    process_files(&source_file, source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+    assert_eq!(mode_of(&dest.path().join("img").join("generated.png")), 0o644);
+
+    let keep = ConvertOptions { image_mode: ImageMode::Keep, ..ConvertOptions::default() };
+    let kept = tempfile::tempdir().unwrap();
+    process_files(&source_file, source.path(), kept.path(), &keep).unwrap();
+    assert_eq!(mode_of(&kept.path().join("img").join("generated.png")), 0o700);
+}
+
+#[test]
+fn test_convert_frontmatter_keywords() {
+    let expected = "keywords:\n- quarto\n- migration\n";
+    assert_eq!(convert_frontmatter(&["keywords: [quarto, migration]"]), expected);
+    assert_eq!(convert_frontmatter(&["keywords:", "  - quarto", "  - migration"]), expected);
+    assert_eq!(convert_frontmatter(&["keywords: quarto, migration,"]), expected);
+    assert_eq!(
+        convert_content("---\ntitle: A\nkeywords: [\"docs: guide\", rust]\n---\n"),
+        "---\ntitle: A\nkeywords:\n- 'docs: guide'\n- rust\n---\n"
+    );
+}
+
+#[test]
+fn test_fail_fast_stops_at_first_failure() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join("a-bad.md"), [0xff, 0xfe]).unwrap();
+    std::fs::write(source.path().join("b-bad.md"), [0xff, 0xfe]).unwrap();
+    std::fs::write(source.path().join("c-good.md"), "# Good\n").unwrap();
+
+    let reports = convert_tree(source.path(), dest.path(), &ConvertOptions { fail_fast: true, ..ConvertOptions::default() }).unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].source_path, source.path().join("a-bad.md"));
+    assert!(!reports[0].is_success());
+    assert!(!dest.path().join("c-good.qmd").exists());
+
+    // The default converts everything and reports both failures
+    let reports = convert_tree(source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+    assert_eq!(reports.iter().filter(|r| !r.is_success()).count(), 2);
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_doc2quarto"))
+        .arg("-s")
+        .arg(source.path())
+        .arg("-d")
+        .arg(tempfile::tempdir().unwrap().path())
+        .arg("--fail-fast")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("0 converted, 0 skipped, 1 failed"));
+}
+
+#[test]
+fn test_convert_tree_category_files_become_section_pages() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let guide = source.path().join("guide");
+    std::fs::create_dir_all(&guide).unwrap();
+    std::fs::write(guide.join("setup.md"), "# Setup\n").unwrap();
+    std::fs::write(
+        guide.join("_category_.json"),
+        r#"{"label": "User Guide", "position": 2, "link": {"type": "generated-index", "description": "Start here"}}"#,
+    )
+    .unwrap();
+    let api = source.path().join("api");
+    std::fs::create_dir_all(&api).unwrap();
+    std::fs::write(api.join("index.md"), "# API\n").unwrap();
+    std::fs::write(api.join("_category_.yml"), "label: API\n").unwrap();
+
+    convert_tree(source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(dest.path().join("guide").join("index.qmd")).unwrap(),
+        "---\ntitle: User Guide\norder: 2\ndescription: Start here\nlisting: default\n---\n"
+    );
+    // An existing index page wins over the category
+    assert_eq!(std::fs::read_to_string(dest.path().join("api").join("index.qmd")).unwrap(), "# API\n");
+    assert!(!dest.path().join("guide").join("_category_.json").exists());
+    assert_eq!(convert_category("{}").unwrap(), None);
+}
+
+#[test]
+fn test_section_pages_follow_versioned_layout_and_existing_output() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let guide = source.path().join("versioned_docs").join("version-1.0").join("guide");
+    std::fs::create_dir_all(&guide).unwrap();
+    std::fs::write(guide.join("setup.md"), "# Setup\n").unwrap();
+    std::fs::write(guide.join("_category_.json"), r#"{"label": "Guide"}"#).unwrap();
+
+    let opts = ConvertOptions { versioned_docs: VersionedDocs::Subdirectories, ..ConvertOptions::default() };
+    convert_tree(source.path(), dest.path(), &opts).unwrap();
+    let index = dest.path().join("1.0").join("guide").join("index.qmd");
+    assert_eq!(std::fs::read_to_string(&index).unwrap(), "---\ntitle: Guide\n---\n");
+    assert!(!dest.path().join("versioned_docs").exists());
+
+    std::fs::write(&index, "# Edited\n").unwrap();
+    let backup = ConvertOptions { existing_output: ExistingOutput::Backup, ..opts };
+    convert_tree(source.path(), dest.path(), &backup).unwrap();
+    assert_eq!(std::fs::read_to_string(index.with_extension("qmd.bak")).unwrap(), "# Edited\n");
+    assert_eq!(std::fs::read_to_string(&index).unwrap(), "---\ntitle: Guide\n---\n");
+}
+
+#[test]
+fn test_unchanged_images_are_not_copied_again() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(source.path().join("img")).unwrap();
+    std::fs::write(source.path().join("img").join("a.png"), b"png a").unwrap();
+    std::fs::write(source.path().join("img").join("b.png"), b"png b").unwrap();
+    let source_file = source.path().join("intro.md");
+    std::fs::write(&source_file, "![A](img/a.png)\n").unwrap();
+
+    let first = process_files(&source_file, source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+    assert_eq!((first.copied_images.len(), first.images_skipped), (2, 0));
+
+    let second = process_files(&source_file, source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+    assert!(second.img_copied);
+    assert_eq!((second.copied_images.len(), second.images_skipped), (0, 2));
+
+    std::fs::write(source.path().join("img").join("b.png"), b"png b, edited").unwrap();
+    let third = process_files(&source_file, source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+    assert_eq!((third.copied_images.len(), third.images_skipped), (1, 1));
+    assert_eq!(std::fs::read(dest.path().join("img").join("b.png")).unwrap(), b"png b, edited");
+
+    let recopy = ConvertOptions { skip_unchanged_images: false, ..ConvertOptions::default() };
+    let forced = process_files(&source_file, source.path(), dest.path(), &recopy).unwrap();
+    assert_eq!((forced.copied_images.len(), forced.images_skipped), (2, 0));
+}
+
+#[test]
+fn test_relocated_images_colliding_with_own_images_are_renamed() {
+    let root = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let source = root.path().join("docs");
+    for (folder, content) in [(source.join("img"), "shared"), (source.join("guide").join("img"), "own")] {
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::write(folder.join("foo.png"), content).unwrap();
+    }
+    std::fs::write(
+        source.join("guide").join("intro.md"),
+        "![Own](img/foo.png) ![Shared](../img/foo.png) <img src=\"../img/foo.png\"/>\n\nWrite `![x](../img/foo.png)` for images.\n",
+    )
+    .unwrap();
+
+    convert_tree(&source, dest.path(), &ConvertOptions::default()).unwrap();
+    let guide = dest.path().join("guide");
+    assert_eq!(
+        std::fs::read_to_string(guide.join("intro.qmd")).unwrap(),
+        "![Own](img/foo.png) ![Shared](img/docs-foo.png) <img src=\"img/docs-foo.png\"/>\n\nWrite `![x](../img/foo.png)` for images.\n"
+    );
+    assert_eq!(std::fs::read_to_string(guide.join("img").join("foo.png")).unwrap(), "own");
+    assert_eq!(std::fs::read_to_string(guide.join("img").join("docs-foo.png")).unwrap(), "shared");
+}
+
+#[test]
+fn test_read_only_images_are_recognized_and_replaced() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let image = source.path().join("img").join("a.png");
+    std::fs::create_dir_all(image.parent().unwrap()).unwrap();
+    std::fs::write(&image, b"png a").unwrap();
+    let set_read_only = |path: &std::path::Path, read_only: bool| {
+        let mut permissions = std::fs::metadata(path).unwrap().permissions();
+        permissions.set_readonly(read_only);
+        std::fs::set_permissions(path, permissions).unwrap();
+    };
+    set_read_only(&image, true);
+    let source_file = source.path().join("intro.md");
+    std::fs::write(&source_file, "![A](img/a.png)\n").unwrap();
+    let copy = dest.path().join("img").join("a.png");
+
+    // The copy keeps the read-only mode, and its time still lets the next run skip it
+    let keep = ConvertOptions { image_mode: ImageMode::Keep, ..ConvertOptions::default() };
+    let first = process_files(&source_file, source.path(), dest.path(), &keep).unwrap();
+    assert_eq!(first.copied_images, std::slice::from_ref(&copy));
+    assert!(std::fs::metadata(&copy).unwrap().permissions().readonly());
+    let second = process_files(&source_file, source.path(), dest.path(), &keep).unwrap();
+    assert_eq!((second.copied_images.len(), second.images_skipped), (0, 1));
+
+    // An edited source replaces the read-only copy
+    set_read_only(&image, false);
+    std::fs::write(&image, b"png a, edited").unwrap();
+    let third = process_files(&source_file, source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+    assert_eq!(third.copied_images, std::slice::from_ref(&copy));
+    assert_eq!(std::fs::read(&copy).unwrap(), b"png a, edited");
+    assert!(!std::fs::metadata(&copy).unwrap().permissions().readonly());
+}
+
+#[test]
+fn test_component_registry_converts_highlight() {
+    let config = Config::from_toml_str("[components]\nHighlight = '[{children}]{style=\"color: {color}\"}'\n").unwrap();
+    let opts = ConvertOptions { components: config.components, ..ConvertOptions::default() };
+    let (converted, warnings) = convert_content_with_report(
+        "I like <Highlight color=\"#25c2a0\">Docusaurus green</Highlight> and `<Highlight>` code.\n<Badge text=\"new\" />\n",
+        &opts,
+    );
+    assert_eq!(
+        converted,
+        "I like [Docusaurus green]{style=\"color: #25c2a0\"} and `<Highlight>` code.\n<Badge text=\"new\" />\n"
+    );
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].kind, WarningKind::ResidualComponent);
+    assert!(warnings[0].message.contains("Badge"));
+
+    // Built-in template, and a block component wrapping whole lines
+    let mut components = std::collections::BTreeMap::new();
+    components.insert("Aside".to_string(), "::: {.column-margin}\n{children}\n:::".to_string());
+    let opts = ConvertOptions { components, ..ConvertOptions::default() };
+    assert_eq!(
+        convert_content_with_options("<Aside>\nSee <Highlight color=\"red\">this</Highlight>.\n</Aside>\n", &opts),
+        "::: {.column-margin}\nSee [this]{style=\"background-color: red\"}.\n:::\n"
+    );
+}
+
+#[test]
+fn test_destination_outside_dest_root_is_rejected() {
+    let sandbox = tempfile::tempdir().unwrap();
+    let source = sandbox.path().join("docs");
+    let dest = sandbox.path().join("site");
+    std::fs::create_dir_all(&source).unwrap();
+    std::fs::create_dir_all(&dest).unwrap();
+    std::fs::write(sandbox.path().join("escape.md"), "# Escape\n").unwrap();
+
+    // `docs/../escape.md` starts with the source root but climbs out of it
+    let crafted = source.join("..").join("escape.md");
+    let error = process_files(&crafted, &source, &dest, &ConvertOptions::default()).unwrap_err();
+    assert!(matches!(error, ConversionError::OutsideDestRoot { .. }));
+    assert!(!sandbox.path().join("escape.qmd").exists());
+
+    #[cfg(unix)]
+    {
+        // A symlinked directory in the destination leads out of the tree
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(source.join("guide")).unwrap();
+        std::fs::write(source.join("guide").join("intro.md"), "# Intro\n").unwrap();
+        std::os::unix::fs::symlink(outside.path(), dest.join("guide")).unwrap();
+
+        let reports = convert_tree(&source, &dest, &ConvertOptions::default()).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert!(!reports[0].is_success());
+        assert!(!outside.path().join("intro.qmd").exists());
+    }
+}
+
+#[test]
+fn test_admonition_title_keeps_markdown() {
+    let input = ":::note **Important** stuff\nBody\n:::\n";
+    assert_eq!(convert_content(input), ":::: {.callout-note}\n## **Important** stuff\nBody\n::::\n");
+    assert_eq!(
+        convert_content_with_title_style(input, CalloutTitleStyle::Attribute),
+        ":::: {.callout-note title=\"**Important** stuff\"}\nBody\n::::\n"
+    );
+    assert_eq!(
+        convert_content_with_title_style(":::tip[Use `\"quoted\"` _values_]\nBody\n:::\n", CalloutTitleStyle::Attribute),
+        ":::: {.callout-tip title=\"Use `\\\"quoted\\\"` _values_\"}\nBody\n::::\n"
+    );
+}
+
+#[test]
+fn test_images_are_copied_in_sorted_order() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let img = source.path().join("img");
+    std::fs::create_dir_all(img.join("b-diagrams")).unwrap();
+    for name in ["c.png", "a.png", "b-diagrams/z.png", "b-diagrams/y.png", "B.png"] {
+        std::fs::write(img.join(name), name).unwrap();
+    }
+    let source_file = source.path().join("intro.md");
+    std::fs::write(&source_file, "# Intro\n").unwrap();
+
+    let report = process_files(&source_file, source.path(), dest.path(), &ConvertOptions::default()).unwrap();
+    let copied: Vec<_> = report
+        .copied_images
+        .iter()
+        .map(|path| path.strip_prefix(dest.path().join("img")).unwrap().to_string_lossy().replace('\\', "/"))
+        .collect();
+    assert_eq!(copied, ["B.png", "a.png", "b-diagrams/y.png", "b-diagrams/z.png", "c.png"]);
+    assert_eq!(report.copied_images.len(), copied.len());
+}
+
+#[test]
+fn test_partial_conversion_keeps_paths_below_source_root() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    let docs = source.path().join("docs");
+    std::fs::create_dir_all(docs.join("guides").join("advanced")).unwrap();
+    std::fs::write(docs.join("intro.md"), "# Intro\n").unwrap();
+    std::fs::write(docs.join("guides").join("setup.md"), "# Setup\n").unwrap();
+    std::fs::write(docs.join("guides").join("advanced").join("tuning.md"), "# Tuning\n").unwrap();
+
+    let opts = ConvertOptions { relative_to: Some(docs.clone()), exclude: vec!["guides/advanced/**".to_string()], ..ConvertOptions::default() };
+    let reports = convert_tree(&docs.join("guides"), dest.path(), &opts).unwrap();
+    assert_eq!(reports.len(), 1);
+    assert!(reports[0].is_success());
+    assert_eq!(reports[0].dest_path, dest.path().join("guides").join("setup.qmd"));
+    assert!(!dest.path().join("intro.qmd").exists());
+    assert!(!dest.path().join("setup.qmd").exists());
+
+    // The converted directory has to be inside the root
+    let outside = ConvertOptions { relative_to: Some(docs.join("guides").join("advanced")), ..ConvertOptions::default() };
+    let error = convert_tree(&docs.join("guides"), dest.path(), &outside).unwrap_err();
+    assert!(matches!(error, ConversionError::SourceOutsideRoot { .. }));
+}
+
+#[test]
+fn test_normalize_footnotes() {
+    let standard = "Claim[^1] and another^[Inline note.] with `[^code sample]`.\n\n[^1]: The source.\n";
+    let opts = ConvertOptions { normalize_footnotes: true, ..ConvertOptions::default() };
+    assert_eq!(convert_content_with_options(standard, &opts), standard);
+
+    let legacy = "Docusaurus[^A site generator by Meta.] is fast[^fast].\n\n[^fast]: Citation needed.\n";
+    assert_eq!(
+        convert_content_with_options(legacy, &opts),
+        "Docusaurus^[A site generator by Meta.] is fast[^fast].\n\n[^fast]: Citation needed.\n"
+    );
+    // The pass is optional, prose such as regex classes could look like legacy notes
+    assert_eq!(convert_content(legacy), legacy);
+}
+
+#[test]
+fn test_convert_frontmatter_map_in_place() {
+    let mut frontmatter: serde_yaml::Mapping = serde_yaml::from_str(
+        "id: intro\nsidebar_label: Intro\nsidebar_position: 3\nhide_title: true\ntags: [docs]\nunlisted: true\ncustom: kept\n",
+    )
+    .unwrap();
+    let mut metadata = serde_yaml::Mapping::new();
+    metadata.insert("format".into(), "html".into());
+    metadata.insert("custom".into(), "default".into());
+    let opts = ConvertOptions { metadata, ..ConvertOptions::default() };
+
+    convert_frontmatter_map(&mut frontmatter, &opts);
+
+    let keys: Vec<&str> = frontmatter.keys().filter_map(|key| key.as_str()).collect();
+    assert_eq!(keys, ["title", "order", "categories", "draft", "custom", "format"]);
+    assert_eq!(frontmatter["title"], serde_yaml::Value::from("Intro"));
+    assert_eq!(frontmatter["order"], serde_yaml::Value::from(3));
+    assert_eq!(frontmatter["draft"], serde_yaml::Value::from(true));
+    assert_eq!(frontmatter["custom"], serde_yaml::Value::from("kept"));
+
+    // Configured drops and the id alias apply as in the string conversion
+    let mut frontmatter: serde_yaml::Mapping = serde_yaml::from_str("id: intro\ncustom_edit_url: null\nsecret: x\n").unwrap();
+    let opts = ConvertOptions { doc_id: DocId::Alias, dropped_frontmatter_keys: vec!["secret".to_string()], ..ConvertOptions::default() };
+    convert_frontmatter_map(&mut frontmatter, &opts);
+    assert_eq!(serde_yaml::to_string(&frontmatter).unwrap(), "custom_edit_url: null\naliases:\n- intro\n");
+}
+
+#[test]
+fn test_cli_plain_progress_lines() {
+    let source = tempfile::tempdir().unwrap();
+    let dest = tempfile::tempdir().unwrap();
+    for index in 0..25 {
+        std::fs::write(source.path().join(format!("page-{:02}.md", index)), "# Page\n").unwrap();
+    }
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_doc2quarto"))
+        .arg("--source")
+        .arg(source.path())
+        .arg("--dest")
+        .arg(dest.path())
+        .arg("--no-progress")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let progress: Vec<&str> = stdout.lines().filter(|line| line.starts_with("Processed ")).collect();
+    // One line every 10% (every 2 files), plus the last file
+    let expected: Vec<String> = (2..=24)
+        .step_by(2)
+        .chain([25])
+        .map(|done| format!("Processed {}/25 ({}%)", done, done * 4))
+        .collect();
+    assert_eq!(progress, expected);
+    assert!(stdout.contains("Conversion completed!"));
+}
+
+#[test]
+fn test_default_callout_types_and_overrides() {
+    let expected = [
+        ("note", "note"),
+        ("tip", "tip"),
+        ("info", "note"),
+        ("caution", "warning"),
+        ("warning", "warning"),
+        ("danger", "important"),
+    ];
+    for (admonition, callout) in expected {
+        assert_eq!(
+            convert_content(&format!(":::{}\nBody\n:::\n", admonition)),
+            format!(":::: {{.callout-{}}}\nBody\n::::\n", callout),
+            "{} should map to {}",
+            admonition,
+            callout
+        );
+    }
+    assert_eq!(
+        convert_content(":::details Show more\nBody\n:::\n"),
+        ":::: {.callout-note collapse=\"true\" title=\"Show more\"}\nBody\n::::\n"
+    );
+    assert_eq!(DEFAULT_CALLOUT_TYPES.len(), expected.len() + 1);
+
+    // Built-in mappings can be overridden like custom types are added
+    let config = Config::from_toml_str("[admonitions]\ncaution = \"caution\"\ninfo = \"tip\"\n").unwrap();
+    let opts = ConvertOptions { admonition_types: config.admonitions, ..ConvertOptions::default() };
+    assert_eq!(
+        convert_content_with_options(":::caution\nA\n:::\n:::INFO\nB\n:::\n:::danger\nC\n:::\n", &opts),
+        ":::: {.callout-caution}\nA\n::::\n:::: {.callout-tip}\nB\n::::\n:::: {.callout-important}\nC\n::::\n"
+    );
+}
+
+#[test]
+fn test_versioned_docs_layouts() {
+    let source = tempfile::tempdir().unwrap();
+    let write = |path: &str, content: &str| {
+        let path = source.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    };
+    write("docs/intro.md", "# Next\n");
+    write("docs/version-2/notes.md", "# Not a version\n");
+    write("versioned_docs/version-1.0/intro.md", "# One\n\nSee [setup](guide/setup.md).\n");
+    write("versioned_docs/version-1.0/guide/setup.md", "# Setup\n");
+    write("versioned_docs/version-2.x/intro.md", "# Two\n");
+    write("versioned_sidebars/version-1.0-sidebars.json", "{}");
+
+    let converted = |versioned_docs: VersionedDocs| {
+        let dest = tempfile::tempdir().unwrap();
+        let opts = ConvertOptions { versioned_docs, ..ConvertOptions::default() };
+        let reports = convert_tree(source.path(), dest.path(), &opts).unwrap();
+        assert!(reports.iter().all(|r| r.is_success()));
+        let mut files: Vec<String> = walkdir::WalkDir::new(dest.path())
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.path().strip_prefix(dest.path()).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+        files.sort();
+        (dest, files)
+    };
+
+    let (_, kept) = converted(VersionedDocs::Keep);
+    assert_eq!(kept, [
+        "docs/intro.qmd",
+        "docs/version-2/notes.qmd",
+        "versioned_docs/version-1.0/guide/setup.qmd",
+        "versioned_docs/version-1.0/intro.qmd",
+        "versioned_docs/version-2.x/intro.qmd",
+    ]);
+
+    let (dest, subdirs) = converted(VersionedDocs::Subdirectories);
+    assert_eq!(subdirs, ["1.0/guide/setup.qmd", "1.0/intro.qmd", "2.x/intro.qmd", "docs/intro.qmd", "docs/version-2/notes.qmd"]);
+    assert!(std::fs::read_to_string(dest.path().join("1.0/intro.qmd")).unwrap().contains("[setup](guide/setup.qmd)"));
+
+    let (dest, profiles) = converted(VersionedDocs::Profiles);
+    assert!(profiles.contains(&"_quarto-1.0.yml".to_string()) && profiles.contains(&"_quarto-2.x.yml".to_string()));
+    assert_eq!(
+        std::fs::read_to_string(dest.path().join("_quarto-1.0.yml")).unwrap(),
+        "project:\n  render:\n    - \"1.0/**/*.qmd\"\n"
+    );
+}
+
+#[test]
+fn test_preserve_crlf_line_endings() {
+    let input = "---\r\ntitle: Intro\r\nsidebar_position: 1\r\n---\r\n\r\n:::tip Note\r\nBody with [link](other.md)\r\n:::\r\n";
+    let opts = ConvertOptions { preserve_line_endings: true, ..ConvertOptions::default() };
+    let expected = "---\ntitle: Intro\norder: 1\n---\n\n:::: {.callout-tip}\n## Note\nBody with [link](other.qmd)\n::::\n";
+
+    assert_eq!(convert_content_with_options(input, &opts), expected.replace('\n', "\r\n"));
+    assert_eq!(convert_content(input), expected);
+    // The dominant ending wins, LF sources stay LF
+    assert_eq!(convert_content_with_options("# A\n\nB\r\nC\n", &opts), "# A\n\nB\nC\n");
+    assert_eq!(convert_content_with_options("# A\r\nB", &opts), "# A\r\nB");
+}